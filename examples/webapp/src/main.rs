@@ -64,14 +64,6 @@ fn create_cache_path_if_absent(cookies: &Cookies) -> PathBuf {
     cache_path
 }
 
-fn remove_cache_path(mut cookies: Cookies) {
-    let cache_path = get_cache_path(&cookies);
-    if cache_path.exists() {
-        fs::remove_file(cache_path).unwrap()
-    }
-    cookies.remove(Cookie::named("uuid"))
-}
-
 fn check_cache_path_exists(cookies: &Cookies) -> bool {
     let cache_path = get_cache_path(cookies);
     cache_path.exists()
@@ -156,8 +148,11 @@ fn index(mut cookies: Cookies) -> AppResponse {
 }
 
 #[get("/sign_out")]
-fn sign_out(cookies: Cookies) -> AppResponse {
-    remove_cache_path(cookies);
+fn sign_out(mut cookies: Cookies) -> AppResponse {
+    // Both the token in memory and its cache file are removed
+    let mut spotify = init_spotify(&cookies);
+    spotify.logout().unwrap();
+    cookies.remove(Cookie::named("uuid"));
     AppResponse::Redirect(Redirect::to("/"))
 }
 
@@ -27,7 +27,7 @@ async fn auth_code_do_things(spotify: &AuthCodeSpotify) {
 
     // Printing the followed artists
     let followed = spotify
-        .current_user_followed_artists(None, None)
+        .current_user_followed_artists_manual(None, None)
         .await
         .expect("couldn't get user followed artists");
     println!(
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::fmt;
+use std::time::Duration;
 
 use maybe_async::maybe_async;
 use serde_json::Value;
@@ -8,6 +9,72 @@ pub type Headers = HashMap<String, String>;
 pub type Query<'a> = HashMap<&'a str, &'a str>;
 pub type Form<'a> = HashMap<&'a str, &'a str>;
 
+/// How the requests that fail because of the rate limit (status code 429) or a
+/// server error (status codes 5xx) are retried.
+///
+/// Requests that are rate limited are always retried, since they weren't
+/// processed. Server errors are only retried for `GET` requests, because a
+/// write may have been applied even if its response is an error, and repeating
+/// it could, e.g., add the same items to a playlist twice.
+///
+/// When rate limited, the time indicated by the `Retry-After` header is
+/// waited. Otherwise, the delay starts at `base_delay` and doubles with every
+/// attempt. In both cases, it's at most `max_delay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of times a request is retried. By default this is
+    /// zero, so requests aren't retried.
+    pub max_retries: u32,
+    /// The delay before the first retry, which is one second by default.
+    pub base_delay: Duration,
+    /// The maximum delay between retries, which is one minute by default.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns how long to wait before retrying the request, or `None` if it
+    /// shouldn't be retried. `method` is the HTTP method of the request, e.g.
+    /// `GET`, `attempt` the number of retries so far, and `retry_after` the
+    /// value of the `Retry-After` header, if any.
+    #[must_use]
+    pub fn delay(
+        &self,
+        method: &str,
+        attempt: u32,
+        status: u16,
+        retry_after: Option<&str>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+
+        let backoff = || {
+            self.base_delay
+                .checked_mul(2u32.saturating_pow(attempt))
+                .unwrap_or(self.max_delay)
+        };
+        let delay = match status {
+            429 => retry_after
+                .and_then(|secs| secs.trim().parse().ok())
+                .map_or_else(backoff, Duration::from_secs),
+            500..=599 if method.eq_ignore_ascii_case("GET") => backoff(),
+            _ => return None,
+        };
+
+        Some(delay.min(self.max_delay))
+    }
+}
+
 /// This trait represents the interface to be implemented for an HTTP client,
 /// which is kept separate from the Spotify client for cleaner code. Thus, it
 /// also requires other basic traits that are needed for the Spotify client.
@@ -44,6 +111,21 @@ pub trait BaseHttpClient: Send + Default + Clone + fmt::Debug {
         payload: &Form<'a>,
     ) -> Result<String, Self::Error>;
 
+    /// Same as [`Self::post_form`], but the headers of the response are
+    /// returned as well, with their names in lowercase.
+    ///
+    /// The default implementation returns no headers, for backwards
+    /// compatibility.
+    async fn post_form_with_headers<'a>(
+        &self,
+        url: &str,
+        headers: Option<&Headers>,
+        payload: &Form<'a>,
+    ) -> Result<(String, Headers), Self::Error> {
+        let response = self.post_form(url, headers, payload).await?;
+        Ok((response, Headers::new()))
+    }
+
     async fn put(
         &self,
         url: &str,
@@ -51,6 +133,21 @@ pub trait BaseHttpClient: Send + Default + Clone + fmt::Debug {
         payload: &Value,
     ) -> Result<String, Self::Error>;
 
+    /// Same as [`Self::put`], but the body is sent as is, with the given
+    /// content type, instead of being serialized into JSON.
+    ///
+    /// The default implementation panics, for backwards compatibility with the
+    /// HTTP clients that don't support raw bodies.
+    async fn put_raw(
+        &self,
+        _url: &str,
+        _headers: Option<&Headers>,
+        _body: &[u8],
+        _content_type: &str,
+    ) -> Result<String, Self::Error> {
+        unimplemented!("raw bodies aren't supported by this HTTP client")
+    }
+
     async fn delete(
         &self,
         url: &str,
@@ -58,3 +155,41 @@ pub trait BaseHttpClient: Send + Default + Clone + fmt::Debug {
         payload: &Value,
     ) -> Result<String, Self::Error>;
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_retry_policy_delay() {
+        let policy = RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+        };
+
+        // Exponential backoff for server errors, capped at the maximum
+        let secs = |secs| Some(Duration::from_secs(secs));
+        assert_eq!(policy.delay("GET", 0, 503, None), secs(1));
+        assert_eq!(policy.delay("GET", 1, 500, None), secs(2));
+        assert_eq!(policy.delay("GET", 2, 502, None), secs(4));
+        assert_eq!(policy.delay("GET", 3, 502, None), None);
+
+        // Writes may have been applied despite server errors
+        assert_eq!(policy.delay("POST", 0, 503, None), None);
+        assert_eq!(policy.delay("PUT", 0, 500, None), None);
+        assert_eq!(policy.delay("DELETE", 0, 502, None), None);
+
+        // The rate limit indicates how long to wait, for any method, and
+        // otherwise the delay grows the same way
+        assert_eq!(policy.delay("GET", 0, 429, Some("3")), secs(3));
+        assert_eq!(policy.delay("PUT", 0, 429, Some("30")), secs(5));
+        assert_eq!(policy.delay("POST", 0, 429, None), secs(1));
+        assert_eq!(policy.delay("POST", 1, 429, None), secs(2));
+        assert_eq!(policy.delay("GET", 2, 429, Some("soon")), secs(4));
+
+        // Other errors aren't retried
+        assert_eq!(policy.delay("GET", 0, 404, None), None);
+        assert_eq!(RetryPolicy::default().delay("GET", 0, 503, None), None);
+    }
+}
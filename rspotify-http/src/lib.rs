@@ -27,7 +27,7 @@ pub use self::ureq::{UreqClient as HttpClient, UreqError as HttpError};
 
 #[cfg(any(feature = "client-reqwest", feature = "client-ureq"))]
 #[cfg(not(all(feature = "client-reqwest", feature = "client-ureq")))]
-pub use common::{BaseHttpClient, Form, Headers, Query};
+pub use common::{BaseHttpClient, Form, Headers, Query, RetryPolicy};
 
 #[cfg(all(feature = "client-reqwest", feature = "client-ureq"))]
 compile_error!(
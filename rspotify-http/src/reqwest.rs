@@ -1,12 +1,12 @@
 //! The client implementation for the reqwest HTTP client, which is async by
 //! default.
 
-use super::{BaseHttpClient, Form, Headers, Query};
+use super::{BaseHttpClient, Form, Headers, Query, RetryPolicy};
 
-use std::convert::TryInto;
+use std::{convert::TryInto, time::Duration};
 
 use maybe_async::async_impl;
-use reqwest::{Method, RequestBuilder};
+use reqwest::{Method, RequestBuilder, Response};
 use serde_json::Value;
 
 /// Custom enum that contains all the possible errors that may occur when using
@@ -53,9 +53,28 @@ pub enum ReqwestError {
 pub struct ReqwestClient {
     /// reqwest needs an instance of its client to perform requests.
     client: reqwest::Client,
+    retry_policy: RetryPolicy,
 }
 
 impl ReqwestClient {
+    /// Initializes the client with a timeout for the requests, if any, and
+    /// the policy for retrying them.
+    #[must_use]
+    pub fn with_options(timeout: Option<Duration>, retry_policy: RetryPolicy) -> Self {
+        let mut builder = reqwest::Client::builder();
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+        let client = builder
+            .build()
+            .expect("Failed to initialize the reqwest client");
+
+        Self {
+            client,
+            retry_policy,
+        }
+    }
+
     async fn request<D>(
         &self,
         method: Method,
@@ -63,6 +82,64 @@ impl ReqwestClient {
         headers: Option<&Headers>,
         add_data: D,
     ) -> Result<String, ReqwestError>
+    where
+        D: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        let response = self.send(method, url, headers, add_data).await?;
+        response.text().await.map_err(Into::into)
+    }
+
+    /// Performs the request and makes sure that the response's status code is
+    /// successful.
+    async fn send<D>(
+        &self,
+        method: Method,
+        url: &str,
+        headers: Option<&Headers>,
+        add_data: D,
+    ) -> Result<Response, ReqwestError>
+    where
+        D: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        let mut attempt = 0;
+        loop {
+            match self
+                .send_once(method.clone(), url, headers, &add_data)
+                .await
+            {
+                Err(ReqwestError::StatusCode(response)) => {
+                    let retry_after = response
+                        .headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|val| val.to_str().ok());
+                    let status = response.status().as_u16();
+                    match self
+                        .retry_policy
+                        .delay(method.as_str(), attempt, status, retry_after)
+                    {
+                        Some(delay) => {
+                            log::warn!(
+                                "Request failed with status {status}, retrying in {delay:?}"
+                            );
+                            tokio::time::sleep(delay).await;
+                            attempt += 1;
+                        }
+                        None => return Err(ReqwestError::StatusCode(response)),
+                    }
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Performs the request a single time.
+    async fn send_once<D>(
+        &self,
+        method: Method,
+        url: &str,
+        headers: Option<&Headers>,
+        add_data: &D,
+    ) -> Result<Response, ReqwestError>
     where
         D: Fn(RequestBuilder) -> RequestBuilder,
     {
@@ -90,7 +167,7 @@ impl ReqwestClient {
 
         // Making sure that the status code is OK
         if response.status().is_success() {
-            response.text().await.map_err(Into::into)
+            Ok(response)
         } else {
             Err(ReqwestError::StatusCode(response))
         }
@@ -134,6 +211,28 @@ impl BaseHttpClient for ReqwestClient {
             .await
     }
 
+    async fn post_form_with_headers<'a>(
+        &self,
+        url: &str,
+        headers: Option<&Headers>,
+        payload: &Form<'a>,
+    ) -> Result<(String, Headers), Self::Error> {
+        let response = self
+            .send(Method::POST, url, headers, |req| req.form(payload))
+            .await?;
+
+        // Header names are already lowercase in reqwest. Values that aren't
+        // valid strings are skipped.
+        let response_headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, val)| Some((name.to_string(), val.to_str().ok()?.to_owned())))
+            .collect();
+        let response = response.text().await?;
+
+        Ok((response, response_headers))
+    }
+
     #[inline]
     async fn put(
         &self,
@@ -145,6 +244,21 @@ impl BaseHttpClient for ReqwestClient {
             .await
     }
 
+    #[inline]
+    async fn put_raw(
+        &self,
+        url: &str,
+        headers: Option<&Headers>,
+        body: &[u8],
+        content_type: &str,
+    ) -> Result<String, Self::Error> {
+        self.request(Method::PUT, url, headers, |req| {
+            req.header(reqwest::header::CONTENT_TYPE, content_type)
+                .body(body.to_vec())
+        })
+        .await
+    }
+
     #[inline]
     async fn delete(
         &self,
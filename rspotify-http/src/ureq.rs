@@ -1,8 +1,8 @@
 //! The client implementation for the ureq HTTP client, which is blocking.
 
-use super::{BaseHttpClient, Form, Headers, Query};
+use super::{BaseHttpClient, Form, Headers, Query, RetryPolicy};
 
-use std::io;
+use std::{io, thread, time::Duration};
 
 use maybe_async::sync_impl;
 use serde_json::Value;
@@ -50,10 +50,37 @@ pub enum UreqError {
     StatusCode(ureq::Response),
 }
 
-#[derive(Default, Debug, Clone)]
-pub struct UreqClient {}
+#[derive(Debug, Clone)]
+pub struct UreqClient {
+    agent: ureq::Agent,
+    retry_policy: RetryPolicy,
+}
+
+impl Default for UreqClient {
+    fn default() -> Self {
+        Self {
+            agent: ureq::Agent::new(),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+}
 
 impl UreqClient {
+    /// Initializes the client with a timeout for the requests, if any, and
+    /// the policy for retrying them.
+    #[must_use]
+    pub fn with_options(timeout: Option<Duration>, retry_policy: RetryPolicy) -> Self {
+        let mut builder = ureq::AgentBuilder::new();
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        Self {
+            agent: builder.build(),
+            retry_policy,
+        }
+    }
+
     /// The request handling in ureq is split in three parts:
     ///
     /// * The initial request (POST, GET, ...) is given as the `request`
@@ -64,10 +91,60 @@ impl UreqClient {
     ///   (JSON, a form...).
     fn request<D>(
         &self,
-        mut request: Request,
+        request: Request,
         headers: Option<&Headers>,
         send_request: D,
     ) -> Result<String, UreqError>
+    where
+        D: Fn(Request) -> Result<Response, ureq::Error>,
+    {
+        let response = self.send(request, headers, send_request)?;
+        response.into_string().map_err(Into::into)
+    }
+
+    /// Performs the request, retrying it according to the retry policy.
+    fn send<D>(
+        &self,
+        request: Request,
+        headers: Option<&Headers>,
+        send_request: D,
+    ) -> Result<Response, UreqError>
+    where
+        D: Fn(Request) -> Result<Response, ureq::Error>,
+    {
+        let mut attempt = 0;
+        loop {
+            match self.send_once(request.clone(), headers, &send_request) {
+                Err(UreqError::StatusCode(response)) => {
+                    let status = response.status();
+                    let retry_after = response.header("retry-after");
+                    let delay =
+                        self.retry_policy
+                            .delay(request.method(), attempt, status, retry_after);
+                    match delay {
+                        Some(delay) => {
+                            log::warn!(
+                                "Request failed with status {status}, retrying in {delay:?}"
+                            );
+                            thread::sleep(delay);
+                            attempt += 1;
+                        }
+                        None => return Err(UreqError::StatusCode(response)),
+                    }
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Performs the request a single time, converting the errors into our
+    /// custom types.
+    fn send_once<D>(
+        &self,
+        mut request: Request,
+        headers: Option<&Headers>,
+        send_request: &D,
+    ) -> Result<Response, UreqError>
     where
         D: Fn(Request) -> Result<Response, ureq::Error>,
     {
@@ -81,7 +158,7 @@ impl UreqClient {
         log::info!("Making request {:?}", request);
         // Converting errors from ureq into our custom error types
         match send_request(request) {
-            Ok(response) => response.into_string().map_err(Into::into),
+            Ok(response) => Ok(response),
             Err(err) => match err {
                 ureq::Error::Status(_, response) => Err(UreqError::StatusCode(response)),
                 ureq::Error::Transport(transport) => Err(UreqError::Transport(transport)),
@@ -101,7 +178,7 @@ impl BaseHttpClient for UreqClient {
         headers: Option<&Headers>,
         payload: &Query,
     ) -> Result<String, Self::Error> {
-        let request = ureq::get(url);
+        let request = self.agent.get(url);
         let sender = |mut req: Request| {
             for (key, val) in payload.iter() {
                 req = req.query(key, val);
@@ -118,7 +195,7 @@ impl BaseHttpClient for UreqClient {
         headers: Option<&Headers>,
         payload: &Value,
     ) -> Result<String, Self::Error> {
-        let request = ureq::post(url);
+        let request = self.agent.post(url);
         let sender = |req: Request| req.send_json(payload.clone());
         self.request(request, headers, sender)
     }
@@ -130,7 +207,7 @@ impl BaseHttpClient for UreqClient {
         headers: Option<&Headers>,
         payload: &Form<'a>,
     ) -> Result<String, Self::Error> {
-        let request = ureq::post(url);
+        let request = self.agent.post(url);
         let sender = |req: Request| {
             let payload = payload
                 .iter()
@@ -143,6 +220,36 @@ impl BaseHttpClient for UreqClient {
         self.request(request, headers, sender)
     }
 
+    fn post_form_with_headers<'a>(
+        &self,
+        url: &str,
+        headers: Option<&Headers>,
+        payload: &Form<'a>,
+    ) -> Result<(String, Headers), Self::Error> {
+        let request = self.agent.post(url);
+        let sender = |req: Request| {
+            let payload = payload
+                .iter()
+                .map(|(key, val)| (*key, *val))
+                .collect::<Vec<_>>();
+
+            req.send_form(&payload)
+        };
+        let response = self.send(request, headers, sender)?;
+
+        let response_headers = response
+            .headers_names()
+            .into_iter()
+            .filter_map(|name| {
+                let val = response.header(&name)?.to_owned();
+                Some((name.to_lowercase(), val))
+            })
+            .collect();
+        let response = response.into_string()?;
+
+        Ok((response, response_headers))
+    }
+
     #[inline]
     fn put(
         &self,
@@ -150,11 +257,24 @@ impl BaseHttpClient for UreqClient {
         headers: Option<&Headers>,
         payload: &Value,
     ) -> Result<String, Self::Error> {
-        let request = ureq::put(url);
+        let request = self.agent.put(url);
         let sender = |req: Request| req.send_json(payload.clone());
         self.request(request, headers, sender)
     }
 
+    #[inline]
+    fn put_raw(
+        &self,
+        url: &str,
+        headers: Option<&Headers>,
+        body: &[u8],
+        content_type: &str,
+    ) -> Result<String, Self::Error> {
+        let request = self.agent.put(url);
+        let sender = |req: Request| req.set("content-type", content_type).send_bytes(body);
+        self.request(request, headers, sender)
+    }
+
     #[inline]
     fn delete(
         &self,
@@ -162,7 +282,7 @@ impl BaseHttpClient for UreqClient {
         headers: Option<&Headers>,
         payload: &Value,
     ) -> Result<String, Self::Error> {
-        let request = ureq::delete(url);
+        let request = self.agent.delete(url);
         let sender = |req: Request| req.send_json(payload.clone());
         self.request(request, headers, sender)
     }
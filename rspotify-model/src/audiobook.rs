@@ -0,0 +1,108 @@
+//! All objects related to audiobook
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::{
+    custom_serde::duration_ms, AudiobookId, ChapterId, Copyright, DatePrecision, Image, Page,
+    ResumePoint,
+};
+
+/// Author object
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Author {
+    pub name: String,
+}
+
+/// Narrator object
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Narrator {
+    pub name: String,
+}
+
+/// Simplified audiobook object
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SimplifiedAudiobook {
+    pub authors: Vec<Author>,
+    #[serde(default)]
+    pub available_markets: Vec<String>,
+    pub copyrights: Vec<Copyright>,
+    pub description: String,
+    pub html_description: String,
+    pub edition: Option<String>,
+    pub explicit: bool,
+    pub external_urls: HashMap<String, String>,
+    pub href: String,
+    pub id: AudiobookId<'static>,
+    pub images: Vec<Image>,
+    pub languages: Vec<String>,
+    pub media_type: String,
+    pub name: String,
+    pub narrators: Vec<Narrator>,
+    pub publisher: String,
+    pub total_chapters: Option<u32>,
+}
+
+/// Full audiobook object
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FullAudiobook {
+    pub authors: Vec<Author>,
+    #[serde(default)]
+    pub available_markets: Vec<String>,
+    pub copyrights: Vec<Copyright>,
+    pub description: String,
+    pub html_description: String,
+    pub edition: Option<String>,
+    pub explicit: bool,
+    pub external_urls: HashMap<String, String>,
+    pub href: String,
+    pub id: AudiobookId<'static>,
+    pub images: Vec<Image>,
+    pub languages: Vec<String>,
+    pub media_type: String,
+    pub name: String,
+    pub narrators: Vec<Narrator>,
+    pub publisher: String,
+    pub total_chapters: Option<u32>,
+    pub chapters: Page<Chapter>,
+}
+
+/// Intermediate audiobooks object wrapped by `Vec`
+#[derive(Deserialize)]
+pub struct AudiobooksPayload {
+    pub audiobooks: Vec<FullAudiobook>,
+}
+
+/// Chapter object. The `audiobook` it belongs to is only included when the
+/// chapter is requested by itself, and not as part of an audiobook.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Chapter {
+    pub audio_preview_url: Option<String>,
+    #[serde(default)]
+    pub available_markets: Vec<String>,
+    pub chapter_number: u32,
+    pub description: String,
+    pub html_description: String,
+    #[serde(with = "duration_ms", rename = "duration_ms")]
+    pub duration: Duration,
+    pub explicit: bool,
+    pub external_urls: HashMap<String, String>,
+    pub href: String,
+    pub id: ChapterId<'static>,
+    pub images: Vec<Image>,
+    pub is_playable: Option<bool>,
+    pub languages: Vec<String>,
+    pub name: String,
+    pub release_date: String,
+    pub release_date_precision: DatePrecision,
+    pub resume_point: Option<ResumePoint>,
+    pub audiobook: Option<SimplifiedAudiobook>,
+}
+
+/// Intermediate chapters object wrapped by `Vec`
+#[derive(Deserialize)]
+pub struct ChaptersPayload {
+    pub chapters: Vec<Chapter>,
+}
@@ -1,18 +1,22 @@
 //! All objects related to the auth flows defined by Spotify API
 
+#[cfg(feature = "encrypted-cache")]
+use crate::encryption::{CacheKey, EncryptedCache};
 use crate::{
     custom_serde::{duration_second, space_separated_scopes},
-    ModelResult,
+    ModelError, ModelResult,
 };
 
 use std::{
     collections::{HashMap, HashSet},
+    ffi::OsString,
     fs,
-    io::{Read, Write},
-    path::Path,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
 };
 
 use chrono::{DateTime, Duration, Utc};
+use fs2::FileExt;
 use serde::{Deserialize, Serialize};
 
 /// Spotify access token information
@@ -26,8 +30,14 @@ pub struct Token {
     #[serde(with = "duration_second")]
     pub expires_in: Duration,
     /// The valid time for which the access token is available represented
-    /// in ISO 8601 combined date and time.
+    /// in ISO 8601 combined date and time. It's on Spotify's clock when the
+    /// token endpoint's response includes its `Date` header.
     pub expires_at: Option<DateTime<Utc>>,
+    /// How far ahead Spotify's clock was of the local one when the token was
+    /// issued, which is added to the local time when checking if the token
+    /// has expired.
+    #[serde(default = "Duration::zero", with = "duration_second")]
+    pub clock_skew: Duration,
     /// A token that can be sent to the Spotify Accounts service
     /// in place of an authorization code
     pub refresh_token: Option<String>,
@@ -48,6 +58,7 @@ impl Default for Token {
             access_token: String::new(),
             expires_in: Duration::seconds(0),
             expires_at: Some(Utc::now()),
+            clock_skew: Duration::zero(),
             refresh_token: None,
             scopes: HashSet::new(),
         }
@@ -56,32 +67,99 @@ impl Default for Token {
 
 impl Token {
     /// Tries to initialize the token from a cache file.
+    ///
+    /// A shared lock is held while reading, so that it doesn't happen at the
+    /// same time as [`Self::write_cache`] in another process.
+    ///
+    /// Encrypted caches are detected as well, but reading them requires a key;
+    /// an error is returned for them. Use `Token::from_encrypted_cache` (with
+    /// the `encrypted-cache` feature) instead, which can read both formats.
     pub fn from_cache<T: AsRef<Path>>(path: T) -> ModelResult<Self> {
-        let mut file = fs::File::open(path)?;
-        let mut tok_str = String::new();
-        file.read_to_string(&mut tok_str)?;
-        let tok = serde_json::from_str(&tok_str)?;
+        let tok_str = read_cache_file(path.as_ref())?;
+        if is_encrypted(&tok_str)? {
+            return Err(ModelError::EncryptedCache(
+                "the token cache is encrypted, but no key was provided".to_owned(),
+            ));
+        }
 
+        let tok = serde_json::from_str(&tok_str)?;
         Ok(tok)
     }
 
     /// Saves the token information into its cache file.
+    ///
+    /// The token is first written to a temporary file next to it, which is
+    /// then renamed into place. This way, the cache is never left half
+    /// written, even if the program crashes in the meanwhile. On Unix, the
+    /// file is only readable by its owner, since it holds the refresh token.
+    ///
+    /// An advisory lock on `<path>.lock` is held during the write, so that
+    /// multiple processes sharing the same cache don't corrupt it.
     pub fn write_cache<T: AsRef<Path>>(&self, path: T) -> ModelResult<()> {
         let token_info = serde_json::to_string(&self)?;
+        write_cache_file(path.as_ref(), token_info.as_bytes())
+    }
+
+    /// Removes the cache file, e.g. when the user signs out. It's not an error
+    /// if it doesn't exist.
+    ///
+    /// The same lock as in [`Self::write_cache`] is held, so that it isn't
+    /// removed in the middle of a write. The lock file itself is kept.
+    pub fn remove_cache<T: AsRef<Path>>(path: T) -> ModelResult<()> {
+        let path = path.as_ref();
+        let lock = open_lock(path)?;
+        lock.lock_exclusive()?;
+
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Tries to initialize the token from a cache file that may be encrypted
+    /// with the given key. Plaintext caches are read as well, so that the ones
+    /// written before enabling encryption keep working.
+    #[cfg(feature = "encrypted-cache")]
+    pub fn from_encrypted_cache<T: AsRef<Path>>(path: T, key: &CacheKey) -> ModelResult<Self> {
+        let tok_str = read_cache_file(path.as_ref())?;
+        if !is_encrypted(&tok_str)? {
+            let tok = serde_json::from_str(&tok_str)?;
+            return Ok(tok);
+        }
 
-        let mut file = fs::OpenOptions::new().write(true).create(true).open(path)?;
-        file.set_len(0)?;
-        file.write_all(token_info.as_bytes())?;
+        let encrypted: EncryptedCache = serde_json::from_str(&tok_str)?;
+        let tok = serde_json::from_slice(&encrypted.decrypt(key)?)?;
+        Ok(tok)
+    }
 
-        Ok(())
+    /// Saves the token information into its cache file, encrypted with the
+    /// given key. The same guarantees as in [`Self::write_cache`] apply.
+    #[cfg(feature = "encrypted-cache")]
+    pub fn write_encrypted_cache<T: AsRef<Path>>(
+        &self,
+        path: T,
+        key: &CacheKey,
+    ) -> ModelResult<()> {
+        let token_info = serde_json::to_vec(&self)?;
+        let encrypted = EncryptedCache::encrypt(&token_info, key)?;
+        let encrypted = serde_json::to_string(&encrypted)?;
+        write_cache_file(path.as_ref(), encrypted.as_bytes())
     }
 
     /// Check if the token is expired. It includes a margin of 10 seconds (which
     /// is how much a request would take in the worst case scenario).
     #[must_use]
     pub fn is_expired(&self) -> bool {
+        self.is_expired_with_margin(Duration::seconds(10))
+    }
+
+    /// Check if the token is expired, or if it will be within the given
+    /// margin. The local time is converted into Spotify's clock with
+    /// [`Self::clock_skew`] first.
+    #[must_use]
+    pub fn is_expired_with_margin(&self, margin: Duration) -> bool {
         self.expires_at.map_or(true, |expiration| {
-            Utc::now() + Duration::seconds(10) >= expiration
+            Utc::now() + self.clock_skew + margin >= expiration
         })
     }
 
@@ -97,6 +175,100 @@ impl Token {
     }
 }
 
+/// Returns the path with `suffix` appended to its file name, e.g.
+/// `.spotify_token_cache.json.lock`.
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path
+        .file_name()
+        .map_or_else(OsString::new, ToOwned::to_owned);
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+/// Whether the cache contents have the encrypted format instead of being a
+/// plaintext token.
+fn is_encrypted(contents: &str) -> ModelResult<bool> {
+    #[derive(Deserialize)]
+    struct Format {
+        ciphertext: Option<serde::de::IgnoredAny>,
+    }
+
+    let format: Format = serde_json::from_str(contents)?;
+    Ok(format.ciphertext.is_some())
+}
+
+/// Reads the cache file at `path` while holding a shared lock.
+///
+/// Reading never requires write access: if the lock file can't be created
+/// (e.g., in a read-only directory), an existing one is opened read-only, and
+/// otherwise the cache is read without the lock.
+fn read_cache_file(path: &Path) -> ModelResult<String> {
+    // Avoids leaving a lock file behind when there's no cache to read
+    if !path.exists() {
+        return Err(
+            io::Error::new(io::ErrorKind::NotFound, "the token cache doesn't exist").into(),
+        );
+    }
+
+    let lock = open_lock(path).or_else(|_| fs::File::open(sibling_path(path, ".lock")));
+    if let Ok(lock) = &lock {
+        lock.lock_shared()?;
+    }
+
+    let mut file = fs::File::open(path)?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+
+    Ok(contents)
+}
+
+/// Atomically replaces the cache file at `path` while holding an exclusive
+/// lock.
+fn write_cache_file(path: &Path, contents: &[u8]) -> ModelResult<()> {
+    let lock = open_lock(path)?;
+    lock.lock_exclusive()?;
+
+    // A leftover temporary file may have wider permissions, so it's replaced
+    // by a new one rather than reused.
+    let tmp_path = sibling_path(path, ".tmp");
+    match fs::remove_file(&tmp_path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(&tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+
+    if let Err(e) = fs::rename(&tmp_path, path) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e.into());
+    }
+
+    Ok(())
+}
+
+/// Opens (or creates) the lock file of the cache at `path`. The lock is
+/// released when the file is closed.
+fn open_lock(path: &Path) -> ModelResult<fs::File> {
+    let lock = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(sibling_path(path, ".lock"))?;
+
+    Ok(lock)
+}
+
 #[cfg(test)]
 mod test {
     use crate::Token;
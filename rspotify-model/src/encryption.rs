@@ -0,0 +1,310 @@
+//! Encryption at rest for the token cache, available with the
+//! `encrypted-cache` feature.
+//!
+//! The token is encrypted with XChaCha20-Poly1305, an authenticated cipher, so
+//! any modification of the cache file is detected when decrypting it. The key
+//! is either supplied by the application or derived from a passphrase with
+//! PBKDF2-HMAC-SHA256, which is slow on purpose, so the derived keys are kept
+//! by the [`Passphrase`] and shared by its clones.
+
+use crate::{ModelError, ModelResult};
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+/// The length in bytes of the keys used to encrypt the cache.
+pub const CACHE_KEY_LENGTH: usize = 32;
+
+/// The number of PBKDF2 iterations used to derive a key from a passphrase,
+/// as recommended by OWASP for PBKDF2-HMAC-SHA256.
+pub const PBKDF2_ITERATIONS: u32 = 600_000;
+
+const CIPHER: &str = "xchacha20poly1305";
+const KDF: &str = "pbkdf2-sha256";
+const NONCE_LENGTH: usize = 24;
+const SALT_LENGTH: usize = 16;
+
+/// The key used to encrypt and decrypt the token cache.
+#[derive(Clone, PartialEq, Eq)]
+pub enum CacheKey {
+    /// A 256-bit key supplied by the application, e.g. obtained from the
+    /// operating system's keyring.
+    Raw([u8; CACHE_KEY_LENGTH]),
+    /// A passphrase from which the key is derived, created with
+    /// [`CacheKey::passphrase`]. A random salt is generated the first time
+    /// it's written, and it's saved next to the ciphertext.
+    Passphrase(Passphrase),
+}
+
+impl CacheKey {
+    /// Initializes a key derived from the given passphrase.
+    #[must_use]
+    pub fn passphrase<S: Into<String>>(passphrase: S) -> Self {
+        CacheKey::Passphrase(Passphrase {
+            passphrase: passphrase.into(),
+            derived: Arc::default(),
+        })
+    }
+}
+
+// The key is never printed, in case the configuration is logged.
+impl fmt::Debug for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheKey::Raw(_) => f.write_str("CacheKey::Raw(..)"),
+            CacheKey::Passphrase(_) => f.write_str("CacheKey::Passphrase(..)"),
+        }
+    }
+}
+
+/// A passphrase from which the key of the cache is derived.
+///
+/// Deriving a key takes a while, so it's only done once for writing, with the
+/// same salt from then on, and once for each different salt read. The derived
+/// keys are shared by the clones of the passphrase, e.g., by the clients
+/// created with the same configuration.
+#[derive(Clone)]
+pub struct Passphrase {
+    passphrase: String,
+    derived: Arc<Mutex<DerivedKeys>>,
+}
+
+#[derive(Default)]
+struct DerivedKeys {
+    /// The key used for writing, along with its salt.
+    write: Option<([u8; SALT_LENGTH], [u8; CACHE_KEY_LENGTH])>,
+    /// The last key derived to read a cache written with another salt or
+    /// number of iterations, e.g., by a different process.
+    read: Option<(Vec<u8>, u32, [u8; CACHE_KEY_LENGTH])>,
+}
+
+impl Passphrase {
+    /// Returns the salt and the key used to encrypt a new cache.
+    fn write_key(&self) -> ModelResult<([u8; SALT_LENGTH], [u8; CACHE_KEY_LENGTH])> {
+        let mut derived = self.derived.lock().unwrap();
+        if let Some(write) = derived.write {
+            return Ok(write);
+        }
+
+        let salt = random_bytes::<SALT_LENGTH>()?;
+        let key = derive_key(&self.passphrase, &salt, PBKDF2_ITERATIONS);
+        derived.write = Some((salt, key));
+        Ok((salt, key))
+    }
+
+    /// Returns the key used to decrypt a cache with the given parameters.
+    fn read_key(&self, salt: &[u8], iterations: u32) -> [u8; CACHE_KEY_LENGTH] {
+        let mut derived = self.derived.lock().unwrap();
+        if let Some((write_salt, key)) = &derived.write {
+            if write_salt == salt && iterations == PBKDF2_ITERATIONS {
+                return *key;
+            }
+        }
+        if let Some((read_salt, read_iterations, key)) = &derived.read {
+            if read_salt == salt && *read_iterations == iterations {
+                return *key;
+            }
+        }
+
+        let key = derive_key(&self.passphrase, salt, iterations);
+        derived.read = Some((salt.to_vec(), iterations, key));
+        key
+    }
+}
+
+// Passphrases are compared without their derived keys.
+impl PartialEq for Passphrase {
+    fn eq(&self, other: &Self) -> bool {
+        self.passphrase == other.passphrase
+    }
+}
+
+impl Eq for Passphrase {}
+
+/// Parameters used to derive the key from a passphrase.
+#[derive(Debug, Serialize, Deserialize)]
+struct Kdf {
+    name: String,
+    salt: String,
+    iterations: u32,
+}
+
+/// The format of an encrypted cache file. Binary fields are encoded in base64.
+#[derive(Debug, Serialize, Deserialize)]
+pub(in crate) struct EncryptedCache {
+    cipher: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kdf: Option<Kdf>,
+    nonce: String,
+    ciphertext: String,
+}
+
+impl EncryptedCache {
+    pub(in crate) fn encrypt(plaintext: &[u8], key: &CacheKey) -> ModelResult<Self> {
+        let (key, kdf) = match key {
+            CacheKey::Raw(key) => (*key, None),
+            CacheKey::Passphrase(passphrase) => {
+                let (salt, key) = passphrase.write_key()?;
+                let kdf = Kdf {
+                    name: KDF.to_owned(),
+                    salt: base64::encode(salt),
+                    iterations: PBKDF2_ITERATIONS,
+                };
+                (key, Some(kdf))
+            }
+        };
+
+        let nonce = random_bytes::<NONCE_LENGTH>()?;
+        let ciphertext = XChaCha20Poly1305::new(Key::from_slice(&key))
+            .encrypt(XNonce::from_slice(&nonce), plaintext)
+            .map_err(|_| ModelError::EncryptedCache("couldn't encrypt the token".to_owned()))?;
+
+        Ok(Self {
+            cipher: CIPHER.to_owned(),
+            kdf,
+            nonce: base64::encode(nonce),
+            ciphertext: base64::encode(ciphertext),
+        })
+    }
+
+    pub(in crate) fn decrypt(&self, key: &CacheKey) -> ModelResult<Vec<u8>> {
+        if self.cipher != CIPHER {
+            return Err(ModelError::EncryptedCache(format!(
+                "unsupported cipher `{}`",
+                self.cipher
+            )));
+        }
+
+        let key = match (key, &self.kdf) {
+            (CacheKey::Raw(key), None) => *key,
+            (CacheKey::Passphrase(passphrase), Some(kdf)) => {
+                if kdf.name != KDF {
+                    return Err(ModelError::EncryptedCache(format!(
+                        "unsupported key derivation function `{}`",
+                        kdf.name
+                    )));
+                }
+                passphrase.read_key(&decode(&kdf.salt)?, kdf.iterations)
+            }
+            (CacheKey::Raw(_), Some(_)) => {
+                return Err(ModelError::EncryptedCache(
+                    "the cache was encrypted with a passphrase, not a raw key".to_owned(),
+                ))
+            }
+            (CacheKey::Passphrase(_), None) => {
+                return Err(ModelError::EncryptedCache(
+                    "the cache was encrypted with a raw key, not a passphrase".to_owned(),
+                ))
+            }
+        };
+
+        let nonce = decode(&self.nonce)?;
+        if nonce.len() != NONCE_LENGTH {
+            return Err(ModelError::EncryptedCache(
+                "invalid nonce length".to_owned(),
+            ));
+        }
+
+        XChaCha20Poly1305::new(Key::from_slice(&key))
+            .decrypt(
+                XNonce::from_slice(&nonce),
+                decode(&self.ciphertext)?.as_ref(),
+            )
+            .map_err(|_| {
+                ModelError::EncryptedCache(
+                    "decryption failed, the key is wrong or the cache was tampered with".to_owned(),
+                )
+            })
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> [u8; CACHE_KEY_LENGTH] {
+    let mut key = [0; CACHE_KEY_LENGTH];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, iterations, &mut key);
+    key
+}
+
+fn random_bytes<const N: usize>() -> ModelResult<[u8; N]> {
+    let mut buf = [0; N];
+    getrandom::getrandom(&mut buf).map_err(|e| ModelError::EncryptedCache(e.to_string()))?;
+    Ok(buf)
+}
+
+fn decode(data: &str) -> ModelResult<Vec<u8>> {
+    base64::decode(data).map_err(|e| ModelError::EncryptedCache(e.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_raw_key_roundtrip() {
+        let key = CacheKey::Raw([7; CACHE_KEY_LENGTH]);
+        let encrypted = EncryptedCache::encrypt(b"secret token", &key).unwrap();
+        assert!(encrypted.kdf.is_none());
+        assert_eq!(encrypted.decrypt(&key).unwrap(), b"secret token");
+
+        let wrong_key = CacheKey::Raw([8; CACHE_KEY_LENGTH]);
+        assert!(encrypted.decrypt(&wrong_key).is_err());
+    }
+
+    #[test]
+    fn test_passphrase_roundtrip() {
+        let key = CacheKey::passphrase("correct horse battery staple");
+        let encrypted = EncryptedCache::encrypt(b"secret token", &key).unwrap();
+        assert!(encrypted.kdf.is_some());
+        assert_eq!(encrypted.decrypt(&key).unwrap(), b"secret token");
+
+        let wrong_key = CacheKey::passphrase("wrong");
+        assert!(encrypted.decrypt(&wrong_key).is_err());
+        assert!(encrypted
+            .decrypt(&CacheKey::Raw([0; CACHE_KEY_LENGTH]))
+            .is_err());
+    }
+
+    /// The key is derived once for writing, and the caches written with it can
+    /// still be read by other instances of the same passphrase.
+    #[test]
+    fn test_passphrase_derived_once() {
+        let key = CacheKey::passphrase("correct horse battery staple");
+        let first = EncryptedCache::encrypt(b"first", &key).unwrap();
+        let second = EncryptedCache::encrypt(b"second", &key.clone()).unwrap();
+        let salt = |cache: &EncryptedCache| cache.kdf.as_ref().unwrap().salt.clone();
+        assert_eq!(salt(&first), salt(&second));
+        assert_ne!(first.nonce, second.nonce);
+
+        let other = CacheKey::passphrase("correct horse battery staple");
+        assert_eq!(other, key);
+        assert_eq!(first.decrypt(&other).unwrap(), b"first");
+        assert_eq!(second.decrypt(&other).unwrap(), b"second");
+        let third = EncryptedCache::encrypt(b"third", &other).unwrap();
+        assert_ne!(salt(&third), salt(&first));
+        assert_eq!(third.decrypt(&key).unwrap(), b"third");
+    }
+
+    #[test]
+    fn test_tampered_ciphertext() {
+        let key = CacheKey::Raw([7; CACHE_KEY_LENGTH]);
+        let mut encrypted = EncryptedCache::encrypt(b"secret token", &key).unwrap();
+        let mut ciphertext = base64::decode(&encrypted.ciphertext).unwrap();
+        ciphertext[0] ^= 1;
+        encrypted.ciphertext = base64::encode(ciphertext);
+        assert!(encrypted.decrypt(&key).is_err());
+    }
+
+    #[test]
+    fn test_key_not_printed() {
+        let key = CacheKey::passphrase("hunter2");
+        assert!(!format!("{:?}", key).contains("hunter2"));
+    }
+}
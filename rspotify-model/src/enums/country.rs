@@ -1,9 +1,9 @@
 use serde::{Deserialize, Serialize};
-use strum::IntoStaticStr;
+use strum::{EnumString, IntoStaticStr};
 
 /// ISO 3166-1 alpha-2 country code, from
 /// [country-list](https://datahub.io/core/country-list)
-#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug, IntoStaticStr)]
+#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug, EnumString, IntoStaticStr)]
 pub enum Country {
     #[strum(serialize = "AF")]
     #[serde(rename = "AF")]
@@ -362,6 +362,11 @@ pub enum Country {
     #[strum(serialize = "KR")]
     #[serde(rename = "KR")]
     KoreaRepublicOf,
+    /// User-assigned code that isn't part of ISO 3166-1, but is one of the
+    /// markets of Spotify.
+    #[strum(serialize = "XK")]
+    #[serde(rename = "XK")]
+    Kosovo,
     #[strum(serialize = "KW")]
     #[serde(rename = "KW")]
     Kuwait,
@@ -101,6 +101,13 @@ impl From<Market> for &'static str {
     }
 }
 
+/// Intermediate markets wrapped by an object. The codes are kept as strings
+/// because Spotify may include some that aren't a [`Country`] yet.
+#[derive(Deserialize)]
+pub struct MarketsPayload {
+    pub markets: Vec<String>,
+}
+
 /// Time limits in miliseconds (unix timestamps)
 #[derive(Clone, Debug, Serialize, Deserialize, Copy, PartialEq, Eq)]
 pub enum TimeLimits {
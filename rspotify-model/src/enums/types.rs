@@ -24,7 +24,8 @@ pub enum AlbumType {
     Compilation,
 }
 
-/// Type: `artist`, `album`, `track`, `playlist`, `show` or `episode`
+/// Type: `artist`, `album`, `track`, `playlist`, `show`, `episode`,
+/// `audiobook` or `chapter`
 #[derive(
     Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug, Display, EnumString, IntoStaticStr,
 )]
@@ -38,6 +39,8 @@ pub enum Type {
     User,
     Show,
     Episode,
+    Audiobook,
+    Chapter,
     Collection,
 }
 
@@ -63,8 +66,9 @@ pub enum CurrentlyPlayingType {
     Unknown,
 }
 
-/// Type for search: `artist`, `album`, `track`, `playlist`, `show`, `episode`
-#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug, IntoStaticStr)]
+/// Type for search: `artist`, `album`, `track`, `playlist`, `show`, `episode`,
+/// `audiobook`
+#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug, Hash, IntoStaticStr)]
 #[serde(rename_all = "snake_case")]
 #[strum(serialize_all = "snake_case")]
 pub enum SearchType {
@@ -74,6 +78,7 @@ pub enum SearchType {
     Playlist,
     Show,
     Episode,
+    Audiobook,
 }
 
 /// The user's Spotify subscription level: `premium`, `free`
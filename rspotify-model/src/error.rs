@@ -31,4 +31,7 @@ pub enum ModelError {
 
     #[error("input/output error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("encrypted cache error: {0}")]
+    EncryptedCache(String),
 }
@@ -15,10 +15,17 @@
 //! * [`Type::User`] => [`UserId`]
 //! * [`Type::Show`] => [`ShowId`]
 //! * [`Type::Episode`] => [`EpisodeId`]
+//! * [`Type::Audiobook`] => [`AudiobookId`]
+//! * [`Type::Chapter`] => [`ChapterId`]
 //!
 //! Every kind of ID defines its own validity function, i.e., what characters it
 //! can be made up of, such as alphanumeric or any.
 //!
+//! They can be parsed from an ID, from an URI like `spotify:track:{id}`, or
+//! from an URL like `https://open.spotify.com/track/{id}`, which is what share
+//! links look like. If the type isn't known in advance, [`AnyId`] parses any
+//! of them.
+//!
 //! These types are just wrappers for [`Cow<str>`], so their usage should be
 //! quite similar overall.
 //!
@@ -85,7 +92,7 @@ use serde::{Deserialize, Serialize};
 use strum::Display;
 use thiserror::Error;
 
-use std::{borrow::Cow, fmt::Debug, hash::Hash};
+use std::{borrow::Cow, convert::TryFrom, fmt, fmt::Debug, hash::Hash, str::FromStr};
 
 use crate::Type;
 
@@ -94,7 +101,8 @@ use crate::Type;
 /// See also [`Id`](crate::idtypes::Id) for details.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Display, Error)]
 pub enum IdError {
-    /// Spotify URI prefix is not `spotify:` or `spotify/`.
+    /// Spotify URI prefix is not `spotify:` or `spotify/`, and it's not an URL
+    /// from `open.spotify.com` either.
     InvalidPrefix,
     /// Spotify URI can't be split into type and id parts (e.g., it has invalid
     /// separator).
@@ -104,6 +112,9 @@ pub enum IdError {
     InvalidType,
     /// Spotify id is invalid (empty or contains invalid characters).
     InvalidId,
+    /// The URL is a short link from `spotify.link`, which redirects to the
+    /// actual URL and thus has to be resolved first.
+    ShortLink,
 }
 
 /// The main interface for an ID.
@@ -167,6 +178,59 @@ pub fn parse_uri(uri: &str) -> Result<(Type, &str), IdError> {
     }
 }
 
+/// A lower level function to parse a Spotify URL into both its type and its
+/// actual ID, the same way as [`parse_uri`]. The ID's validity isn't checked
+/// either.
+///
+/// The URL must be in the format `https://open.spotify.com/{type}/{id}`, which
+/// is what the share links look like. Its query and fragment (e.g.,
+/// `?si=...`) are ignored, and so is the localized path segment, as in
+/// `https://open.spotify.com/intl-de/album/{id}`.
+///
+/// Short links such as `https://spotify.link/...` are redirections whose ID
+/// can't be known without following them, so they are rejected with
+/// `IdError::ShortLink`. Their redirection has to be followed first, e.g. with
+/// an HTTP client, to obtain the full URL.
+pub fn parse_url(url: &str) -> Result<(Type, &str), IdError> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or(IdError::InvalidPrefix)?;
+    if rest.starts_with("spotify.link/") {
+        return Err(IdError::ShortLink);
+    }
+    let path = rest
+        .strip_prefix("open.spotify.com/")
+        .ok_or(IdError::InvalidPrefix)?;
+
+    // The query and the fragment aren't part of the ID
+    let path = match path.find(['?', '#']) {
+        Some(end) => &path[..end],
+        None => path,
+    };
+
+    let mut segments = path.split('/').peekable();
+    if let Some(locale) = segments.peek().and_then(|s| s.strip_prefix("intl-")) {
+        let is_locale = !locale.is_empty()
+            && locale
+                .chars()
+                .all(|ch| ch.is_ascii_alphabetic() || ch == '-');
+        if !is_locale {
+            return Err(IdError::InvalidFormat);
+        }
+        segments.next();
+    }
+
+    let (tpe, id) = match (segments.next(), segments.next(), segments.next()) {
+        (Some(tpe), Some(id), None) if !id.is_empty() => (tpe, id),
+        _ => return Err(IdError::InvalidFormat),
+    };
+    match tpe.parse::<Type>() {
+        Ok(tpe) => Ok((tpe, id)),
+        _ => Err(IdError::InvalidType),
+    }
+}
+
 /// This macro helps consistently define ID types.
 ///
 /// * The `$type` parameter indicates what variant in `Type` the ID is for (say,
@@ -275,7 +339,44 @@ macro_rules! define_idtypes {
                     }
                 }
 
-                /// Parse Spotify ID or URI from string slice
+                /// Parse Spotify URL from string slice
+                ///
+                /// Spotify URL must be in the format
+                /// `https://open.spotify.com/{type}/{id}`, optionally with a
+                /// localized segment before the type and a query or fragment
+                /// after the ID. See [`parse_url`] for the details.
+                ///
+                /// Examples:
+                /// `https://open.spotify.com/track/4y4VO05kYgUTo2bzbox1an?si=a1b2`,
+                /// `https://open.spotify.com/intl-de/album/6IcGNaXFRf5Y1jc7QsE9O2`.
+                ///
+                /// # Errors
+                ///
+                /// - `IdError::InvalidPrefix` - if `url` is not an URL from
+                ///   `open.spotify.com`,
+                /// - `IdError::ShortLink` - if `url` is a short link from
+                ///   `spotify.link`, which has to be resolved first,
+                /// - `IdError::InvalidType` - if type part of an `url` is not a
+                ///   valid Spotify type `T`,
+                /// - `IdError::InvalidId` - if id part of an `url` is not a
+                ///   valid id,
+                /// - `IdError::InvalidFormat` - if its path can't be split into
+                ///   type and id parts.
+                ///
+                /// # Implementation details
+                ///
+                /// Unlike [`Self::from_id`], this method takes a `&str` for the
+                /// same reasons as [`Self::from_uri`].
+                pub fn from_url(url: &'a str) -> Result<Self, IdError> {
+                    let (tpe, id) = parse_url(url)?;
+                    if tpe == Type::$type {
+                        Self::from_id(id)
+                    } else {
+                        Err(IdError::InvalidType)
+                    }
+                }
+
+                /// Parse Spotify ID, URI or URL from string slice
                 ///
                 /// Spotify URI must be in one of the following formats:
                 /// `spotify:{type}:{id}` or `spotify/{type}/{id}`.
@@ -288,19 +389,22 @@ macro_rules! define_idtypes {
                 /// Examples: `spotify:album:6IcGNaXFRf5Y1jc7QsE9O2`,
                 /// `spotify/track/4y4VO05kYgUTo2bzbox1an`.
                 ///
-                /// If input string is not a valid Spotify URI (it's not started
-                /// with `spotify:` or `spotify/`), it must be a valid Spotify
-                /// object ID, i.e. a non-empty valid string.
+                /// Spotify URLs are accepted as well, in the formats described
+                /// in [`Self::from_url`].
+                ///
+                /// If input string is neither a valid Spotify URI (it's not
+                /// started with `spotify:` or `spotify/`) nor an URL, it must
+                /// be a valid Spotify object ID, i.e. a non-empty valid string.
                 ///
                 /// # Errors
                 ///
-                /// - `IdError::InvalidType` - if `id_or_uri` is an URI, and
-                ///   it's type part is not equal to `T`,
-                /// - `IdError::InvalidId` - either if `id_or_uri` is an URI
-                ///   with invalid id part, or it's an invalid id (id is invalid
-                ///   if it contains valid characters),
-                /// - `IdError::InvalidFormat` - if `id_or_uri` is an URI, and
-                ///   it can't be split into type and id parts.
+                /// - `IdError::InvalidType` - if `id_or_uri` is an URI or URL,
+                ///   and it's type part is not equal to `T`,
+                /// - `IdError::InvalidId` - either if `id_or_uri` is an URI or
+                ///   URL with invalid id part, or it's an invalid id (id is
+                ///   invalid if it contains valid characters),
+                /// - `IdError::InvalidFormat` - if `id_or_uri` is an URI or
+                ///   URL, and it can't be split into type and id parts.
                 ///
                 /// # Implementation details
                 ///
@@ -312,9 +416,12 @@ macro_rules! define_idtypes {
                 /// sensible choice is to just use a `&str`.
                 pub fn from_id_or_uri(id_or_uri: &'a str) -> Result<Self, IdError> {
                     match Self::from_uri(id_or_uri) {
-                        Ok(id) => Ok(id),
+                        Err(IdError::InvalidPrefix) => {}
+                        result => return result,
+                    }
+                    match Self::from_url(id_or_uri) {
                         Err(IdError::InvalidPrefix) => Self::from_id(id_or_uri),
-                        Err(error) => Err(error),
+                        result => result,
                     }
                 }
 
@@ -367,7 +474,7 @@ macro_rules! define_idtypes {
                             &self, formatter: &mut std::fmt::Formatter<'_>
                         ) -> Result<(), std::fmt::Error>
                         {
-                            let msg = concat!("ID or URI for struct ", stringify!($name));
+                            let msg = concat!("ID, URI or URL for struct ", stringify!($name));
                             formatter.write_str(msg)
                         }
 
@@ -454,6 +561,14 @@ define_idtypes!(
         name: EpisodeId,
         validity: |id| id.chars().all(|ch| ch.is_ascii_alphanumeric())
     },
+    Audiobook => {
+        name: AudiobookId,
+        validity: |id| id.chars().all(|ch| ch.is_ascii_alphanumeric())
+    },
+    Chapter => {
+        name: ChapterId,
+        validity: |id| id.chars().all(|ch| ch.is_ascii_alphanumeric())
+    },
     User => {
         name: UserId,
         validity: |_| true
@@ -504,30 +619,6 @@ impl<'a> PlayContextId<'a> {
     }
 }
 
-// Spotify ID or URI parsing error
-///
-/// See also [`Id`](crate::idtypes::Id) for details.
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Display, Error)]
-pub enum IdError {
-    /// Spotify URI prefix is not `spotify:` or `spotify/`.
-    InvalidPrefix,
-    /// Spotify URI can't be split into type and id parts (e.g., it has invalid
-    /// separator).
-    InvalidFormat,
-    /// Spotify URI has invalid type name, or id has invalid type in a given
-    /// context (e.g. a method expects a track id, but artist id is provided).
-    InvalidType,
-    /// Spotify id is invalid (empty or contains invalid characters).
-    InvalidId,
-}
-
-/// The main interface for an ID.
-///
-/// See the [module level documentation] for more information.
-///
-/// [module level documentation]: [`crate::idtypes`]
-#[enum_dispatch]
-
 /// Grouping up multiple kinds of IDs to treat them generically. This also
 /// implements [`Id`] and [`From`] to instantiate it.
 #[enum_dispatch(Id)]
@@ -562,6 +653,320 @@ impl<'a> PlayableId<'a> {
     }
 }
 
+/// Any kind of Spotify ID, for when its type isn't known until it's parsed,
+/// e.g., for URIs coming from the user or from a database.
+///
+/// It's parsed from an URI or an URL with [`FromStr`], [`Self::from_uri`] or
+/// [`Self::from_url`]. Besides the regular `spotify:{type}:{id}` URIs, it
+/// also recognizes:
+///
+/// * Local files, as in `spotify:local:{artist}:{album}:{title}:{duration}`,
+///   which have no ID and are kept as their full URI.
+/// * The legacy playlist URIs, as in `spotify:user:{user}:playlist:{id}`. The
+///   user is discarded, since the playlist ID is enough.
+/// * The saved tracks of a user, as in `spotify:user:{user}:collection`.
+///
+/// Like in [`parse_uri`], all of them may use slashes instead of colons, as in
+/// `spotify/local/{artist}/{album}/{title}/{duration}`.
+///
+/// Both [`Display`](std::fmt::Display) and serialization show its URI, so
+/// that it can be parsed back. It can be converted into the specific ID types
+/// with [`TryFrom`], which fails with `IdError::InvalidType` when the types
+/// don't match.
+///
+/// ```
+/// use rspotify_model::{idtypes::Id, AnyId, TrackId};
+/// use std::convert::TryFrom;
+///
+/// let id: AnyId = "spotify:track:4iV5W9uYEdYUVa79Axb7Rh".parse().unwrap();
+/// let track = TrackId::try_from(id).unwrap();
+/// assert_eq!(track.id(), "4iV5W9uYEdYUVa79Axb7Rh");
+///
+/// let id: AnyId = "spotify:user:spotify:playlist:37i9dQZF1DXcBWIGoYBM5M"
+///     .parse()
+///     .unwrap();
+/// assert_eq!(id.to_string(), "spotify:playlist:37i9dQZF1DXcBWIGoYBM5M");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AnyId<'a> {
+    Artist(ArtistId<'a>),
+    Album(AlbumId<'a>),
+    Track(TrackId<'a>),
+    Playlist(PlaylistId<'a>),
+    User(UserId<'a>),
+    Show(ShowId<'a>),
+    Episode(EpisodeId<'a>),
+    Audiobook(AudiobookId<'a>),
+    Chapter(ChapterId<'a>),
+    /// The saved tracks of the given user
+    Collection(UserId<'a>),
+    /// The full URI of a local file
+    Local(Cow<'a, str>),
+}
+
+impl<'a> AnyId<'a> {
+    /// Parse any Spotify URI from string slice.
+    ///
+    /// Apart from the formats accepted by [`parse_uri`], it recognizes the
+    /// local files, the legacy playlist URIs and the users' collections, as
+    /// described in the [type-level docs](Self).
+    ///
+    /// # Errors
+    ///
+    /// - `IdError::InvalidPrefix` - if `uri` is not started with
+    ///   `spotify:` or `spotify/`,
+    /// - `IdError::InvalidType` - if type part of an `uri` is not a valid
+    ///   Spotify type, or if it's `collection` without a user,
+    /// - `IdError::InvalidId` - if id part of an `uri` is not a valid id,
+    /// - `IdError::InvalidFormat` - if it can't be split into type and id
+    ///   parts, or if it's a local file without its four fields.
+    pub fn from_uri(uri: &'a str) -> Result<Self, IdError> {
+        // Either colons or slashes, the same separator in the whole URI
+        let sep = match uri
+            .strip_prefix("spotify")
+            .and_then(|rest| rest.chars().next())
+        {
+            Some(sep) if sep == ':' || sep == '/' => sep,
+            _ => return Err(IdError::InvalidPrefix),
+        };
+        let parts_after = |tpe: &str| {
+            uri.strip_prefix(&format!("spotify{sep}{tpe}{sep}"))
+                .map(|rest| rest.split(sep).collect::<Vec<_>>())
+        };
+
+        if let Some(fields) = parts_after("local") {
+            // Artist, album, title and duration in seconds, with the text
+            // URL-encoded so that it contains no separators.
+            return match fields.as_slice() {
+                [_, _, _, duration]
+                    if !duration.is_empty() && duration.chars().all(|ch| ch.is_ascii_digit()) =>
+                {
+                    Ok(AnyId::Local(Cow::Borrowed(uri)))
+                }
+                _ => Err(IdError::InvalidFormat),
+            };
+        }
+
+        if let Some(parts) = parts_after("user") {
+            return match parts.as_slice() {
+                [user] => UserId::from_id(*user).map(AnyId::User),
+                [user, "collection"] => UserId::from_id(*user).map(AnyId::Collection),
+                [_, "playlist", id] => PlaylistId::from_id(*id).map(AnyId::Playlist),
+                _ => Err(IdError::InvalidFormat),
+            };
+        }
+
+        let (tpe, id) = parse_uri(uri)?;
+        Self::from_type_and_id(tpe, id)
+    }
+
+    /// Parse any Spotify URL from string slice, in the formats accepted by
+    /// [`parse_url`].
+    ///
+    /// # Errors
+    ///
+    /// The same as [`parse_url`], and `IdError::InvalidId` if the id part of
+    /// an `url` is not a valid id.
+    pub fn from_url(url: &'a str) -> Result<Self, IdError> {
+        let (tpe, id) = parse_url(url)?;
+        Self::from_type_and_id(tpe, id)
+    }
+
+    fn from_type_and_id(tpe: Type, id: &'a str) -> Result<Self, IdError> {
+        match tpe {
+            Type::Artist => ArtistId::from_id(id).map(AnyId::Artist),
+            Type::Album => AlbumId::from_id(id).map(AnyId::Album),
+            Type::Track => TrackId::from_id(id).map(AnyId::Track),
+            Type::Playlist => PlaylistId::from_id(id).map(AnyId::Playlist),
+            Type::User => UserId::from_id(id).map(AnyId::User),
+            Type::Show => ShowId::from_id(id).map(AnyId::Show),
+            Type::Episode => EpisodeId::from_id(id).map(AnyId::Episode),
+            Type::Audiobook => AudiobookId::from_id(id).map(AnyId::Audiobook),
+            Type::Chapter => ChapterId::from_id(id).map(AnyId::Chapter),
+            // The collections always belong to a user
+            Type::Collection => Err(IdError::InvalidType),
+        }
+    }
+
+    /// The type of the ID, or `None` for local files.
+    #[must_use]
+    pub fn _type(&self) -> Option<Type> {
+        match self {
+            AnyId::Artist(x) => Some(x._type()),
+            AnyId::Album(x) => Some(x._type()),
+            AnyId::Track(x) => Some(x._type()),
+            AnyId::Playlist(x) => Some(x._type()),
+            AnyId::User(x) => Some(x._type()),
+            AnyId::Show(x) => Some(x._type()),
+            AnyId::Episode(x) => Some(x._type()),
+            AnyId::Audiobook(x) => Some(x._type()),
+            AnyId::Chapter(x) => Some(x._type()),
+            AnyId::Collection(_) => Some(Type::Collection),
+            AnyId::Local(_) => None,
+        }
+    }
+
+    /// Returns the URI of the ID, which can be parsed back into an `AnyId`.
+    #[must_use]
+    pub fn uri(&self) -> String {
+        match self {
+            AnyId::Artist(x) => x.uri(),
+            AnyId::Album(x) => x.uri(),
+            AnyId::Track(x) => x.uri(),
+            AnyId::Playlist(x) => x.uri(),
+            AnyId::User(x) => x.uri(),
+            AnyId::Show(x) => x.uri(),
+            AnyId::Episode(x) => x.uri(),
+            AnyId::Audiobook(x) => x.uri(),
+            AnyId::Chapter(x) => x.uri(),
+            AnyId::Collection(x) => format!("{}:collection", x.uri()),
+            AnyId::Local(uri) => uri.to_string(),
+        }
+    }
+
+    #[must_use]
+    pub fn as_ref(&'a self) -> Self {
+        match self {
+            AnyId::Artist(x) => AnyId::Artist(x.as_ref()),
+            AnyId::Album(x) => AnyId::Album(x.as_ref()),
+            AnyId::Track(x) => AnyId::Track(x.as_ref()),
+            AnyId::Playlist(x) => AnyId::Playlist(x.as_ref()),
+            AnyId::User(x) => AnyId::User(x.as_ref()),
+            AnyId::Show(x) => AnyId::Show(x.as_ref()),
+            AnyId::Episode(x) => AnyId::Episode(x.as_ref()),
+            AnyId::Audiobook(x) => AnyId::Audiobook(x.as_ref()),
+            AnyId::Chapter(x) => AnyId::Chapter(x.as_ref()),
+            AnyId::Collection(x) => AnyId::Collection(x.as_ref()),
+            AnyId::Local(uri) => AnyId::Local(Cow::Borrowed(uri)),
+        }
+    }
+
+    #[must_use]
+    pub fn into_static(self) -> AnyId<'static> {
+        match self {
+            AnyId::Artist(x) => AnyId::Artist(x.into_static()),
+            AnyId::Album(x) => AnyId::Album(x.into_static()),
+            AnyId::Track(x) => AnyId::Track(x.into_static()),
+            AnyId::Playlist(x) => AnyId::Playlist(x.into_static()),
+            AnyId::User(x) => AnyId::User(x.into_static()),
+            AnyId::Show(x) => AnyId::Show(x.into_static()),
+            AnyId::Episode(x) => AnyId::Episode(x.into_static()),
+            AnyId::Audiobook(x) => AnyId::Audiobook(x.into_static()),
+            AnyId::Chapter(x) => AnyId::Chapter(x.into_static()),
+            AnyId::Collection(x) => AnyId::Collection(x.into_static()),
+            AnyId::Local(uri) => AnyId::Local(Cow::Owned(uri.into_owned())),
+        }
+    }
+
+    #[must_use]
+    pub fn clone_static(&'a self) -> AnyId<'static> {
+        self.clone().into_static()
+    }
+}
+
+impl FromStr for AnyId<'static> {
+    type Err = IdError;
+
+    /// Parses either an URI or an URL.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = match AnyId::from_uri(s) {
+            Err(IdError::InvalidPrefix) => AnyId::from_url(s)?,
+            result => result?,
+        };
+        Ok(id.into_static())
+    }
+}
+
+/// Displaying the ID shows its URI
+impl fmt::Display for AnyId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.uri())
+    }
+}
+
+// Unlike the specific IDs, it's serialized as its URI so that the type isn't
+// lost.
+impl Serialize for AnyId<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for AnyId<'static> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let uri = String::deserialize(deserializer)?;
+        uri.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// This helps define the conversions between `AnyId` and the specific IDs.
+macro_rules! impl_any_id_conversions {
+    ($($variant:ident => $name:ident),+) => {
+        $(
+            impl<'a> From<$name<'a>> for AnyId<'a> {
+                fn from(id: $name<'a>) -> Self {
+                    AnyId::$variant(id)
+                }
+            }
+
+            impl<'a> TryFrom<AnyId<'a>> for $name<'a> {
+                type Error = IdError;
+
+                fn try_from(id: AnyId<'a>) -> Result<Self, Self::Error> {
+                    match id {
+                        AnyId::$variant(id) => Ok(id),
+                        _ => Err(IdError::InvalidType),
+                    }
+                }
+            }
+        )+
+    }
+}
+
+impl_any_id_conversions!(
+    Artist => ArtistId,
+    Album => AlbumId,
+    Track => TrackId,
+    Playlist => PlaylistId,
+    User => UserId,
+    Show => ShowId,
+    Episode => EpisodeId,
+    Audiobook => AudiobookId,
+    Chapter => ChapterId
+);
+
+impl<'a> TryFrom<AnyId<'a>> for PlayContextId<'a> {
+    type Error = IdError;
+
+    fn try_from(id: AnyId<'a>) -> Result<Self, Self::Error> {
+        match id {
+            AnyId::Artist(id) => Ok(PlayContextId::Artist(id)),
+            AnyId::Album(id) => Ok(PlayContextId::Album(id)),
+            AnyId::Playlist(id) => Ok(PlayContextId::Playlist(id)),
+            AnyId::Show(id) => Ok(PlayContextId::Show(id)),
+            _ => Err(IdError::InvalidType),
+        }
+    }
+}
+
+impl<'a> TryFrom<AnyId<'a>> for PlayableId<'a> {
+    type Error = IdError;
+
+    fn try_from(id: AnyId<'a>) -> Result<Self, Self::Error> {
+        match id {
+            AnyId::Track(id) => Ok(PlayableId::Track(id)),
+            AnyId::Episode(id) => Ok(PlayableId::Episode(id)),
+            _ => Err(IdError::InvalidType),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -571,6 +976,7 @@ mod test {
     const ID: &str = "4iV5W9uYEdYUVa79Axb7Rh";
     const URI: &str = "spotify:track:4iV5W9uYEdYUVa79Axb7Rh";
     const URI_SLASHES: &str = "spotify/track/4iV5W9uYEdYUVa79Axb7Rh";
+    const URL: &str = "https://open.spotify.com/track/4iV5W9uYEdYUVa79Axb7Rh?si=1a2b3c4d5e6f";
     // Invalid values:
     const URI_EMPTY: &str = "spotify::4iV5W9uYEdYUVa79Axb7Rh";
     const URI_WRONGTYPE1: &str = "spotify:unknown:4iV5W9uYEdYUVa79Axb7Rh";
@@ -602,7 +1008,80 @@ mod test {
         assert_eq!(TrackId::from_uri(URI_MIXED2), Err(IdError::InvalidFormat));
     }
 
-    /// Deserialization should accept both IDs and URIs as well.
+    #[test]
+    fn test_url_parse() {
+        let valid = [
+            "https://open.spotify.com/track/4iV5W9uYEdYUVa79Axb7Rh",
+            "http://open.spotify.com/track/4iV5W9uYEdYUVa79Axb7Rh",
+            "https://open.spotify.com/track/4iV5W9uYEdYUVa79Axb7Rh?si=1a2b3c4d5e6f",
+            "https://open.spotify.com/track/4iV5W9uYEdYUVa79Axb7Rh#details",
+            "https://open.spotify.com/intl-de/track/4iV5W9uYEdYUVa79Axb7Rh",
+            "https://open.spotify.com/intl-pt-BR/track/4iV5W9uYEdYUVa79Axb7Rh?si=x",
+        ];
+        for url in valid {
+            assert_eq!(TrackId::from_url(url).unwrap().id(), ID, "{url}");
+        }
+
+        let id = TrackId::from_id(ID).unwrap();
+        assert_eq!(TrackId::from_url(&id.url()), Ok(id.clone()));
+
+        let invalid = [
+            (ID, IdError::InvalidPrefix),
+            (URI, IdError::InvalidPrefix),
+            (
+                "open.spotify.com/track/4iV5W9uYEdYUVa79Axb7Rh",
+                IdError::InvalidPrefix,
+            ),
+            (
+                "ftp://open.spotify.com/track/4iV5W9uYEdYUVa79Axb7Rh",
+                IdError::InvalidPrefix,
+            ),
+            (
+                "https://example.com/track/4iV5W9uYEdYUVa79Axb7Rh",
+                IdError::InvalidPrefix,
+            ),
+            (
+                "https://open.spotify.com.evil/track/4iV5W9uYEdYUVa79Axb7Rh",
+                IdError::InvalidPrefix,
+            ),
+            ("https://spotify.link/Ab3dEfGhIjK", IdError::ShortLink),
+            ("https://open.spotify.com/track", IdError::InvalidFormat),
+            ("https://open.spotify.com/track/", IdError::InvalidFormat),
+            (
+                "https://open.spotify.com/track/4iV5W9uYEdYUVa79Axb7Rh/",
+                IdError::InvalidFormat,
+            ),
+            (
+                "https://open.spotify.com/intl-/track/4iV5W9uYEdYUVa79Axb7Rh",
+                IdError::InvalidFormat,
+            ),
+            (
+                "https://open.spotify.com/intl-d3/track/4iV5W9uYEdYUVa79Axb7Rh",
+                IdError::InvalidFormat,
+            ),
+            (
+                "https://open.spotify.com/de/track/4iV5W9uYEdYUVa79Axb7Rh",
+                IdError::InvalidFormat,
+            ),
+            (
+                "https://open.spotify.com/unknown/4iV5W9uYEdYUVa79Axb7Rh",
+                IdError::InvalidType,
+            ),
+            (
+                "https://open.spotify.com/album/4iV5W9uYEdYUVa79Axb7Rh",
+                IdError::InvalidType,
+            ),
+            (
+                "https://open.spotify.com/track/4iV5W9uYEdYU-a79Axb7Rh",
+                IdError::InvalidId,
+            ),
+        ];
+        for (url, error) in invalid {
+            assert_eq!(TrackId::from_url(url), Err(error), "{url}");
+        }
+    }
+
+    /// Deserialization should accept IDs, URIs and URLs as well.
     #[test]
     fn test_id_or_uri_and_deserialize() {
         fn test_any<F, E>(check: F)
@@ -618,6 +1097,8 @@ mod test {
             assert_eq!(check(URI).unwrap().id(), ID);
             assert!(check(URI_SLASHES).is_ok());
             assert_eq!(check(URI_SLASHES).unwrap().id(), ID);
+            assert!(check(URL).is_ok());
+            assert_eq!(check(URL).unwrap().id(), ID);
 
             // These should not work in any case
             assert!(check(URI_SHORT).is_err());
@@ -625,6 +1106,8 @@ mod test {
             assert!(check(URI_WRONGTYPE1).is_err());
             assert!(check(URI_MIXED1).is_err());
             assert!(check(URI_MIXED2).is_err());
+            assert!(check("https://spotify.link/Ab3dEfGhIjK").is_err());
+            assert!(check("https://open.spotify.com/album/4iV5W9uYEdYUVa79Axb7Rh").is_err());
         }
 
         // Easily testing both ways to obtain an ID
@@ -635,6 +1118,137 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_any_id_parse() {
+        let parsed = |s: &str| s.parse::<AnyId>();
+
+        let track = TrackId::from_id(ID).unwrap();
+        assert_eq!(parsed(URI), Ok(AnyId::Track(track.clone())));
+        assert_eq!(parsed(URI_SLASHES), Ok(AnyId::Track(track.clone())));
+        assert_eq!(parsed(&track.url()), Ok(AnyId::Track(track)));
+        assert_eq!(
+            parsed("spotify:chapter:0IsXVP0JmcB2adSE338GkK"),
+            Ok(AnyId::Chapter(
+                ChapterId::from_id("0IsXVP0JmcB2adSE338GkK").unwrap()
+            ))
+        );
+
+        let playlist = PlaylistId::from_id("37i9dQZF1DXcBWIGoYBM5M").unwrap();
+        assert_eq!(
+            parsed("spotify:user:spotify:playlist:37i9dQZF1DXcBWIGoYBM5M"),
+            Ok(AnyId::Playlist(playlist))
+        );
+        let user = UserId::from_id("wizzler").unwrap();
+        assert_eq!(
+            parsed("spotify:user:wizzler"),
+            Ok(AnyId::User(user.clone()))
+        );
+        assert_eq!(
+            parsed("spotify:user:wizzler:collection"),
+            Ok(AnyId::Collection(user.clone()))
+        );
+        assert_eq!(
+            parsed("spotify/user/wizzler/collection"),
+            Ok(AnyId::Collection(user))
+        );
+
+        let local = "spotify:local:Daft+Punk:Discovery:One+More+Time:320";
+        assert_eq!(parsed(local), Ok(AnyId::Local(Cow::Borrowed(local))));
+        let local = "spotify/local/Daft+Punk/Discovery/One+More+Time/320";
+        assert_eq!(parsed(local), Ok(AnyId::Local(Cow::Borrowed(local))));
+        assert_eq!(
+            parsed("spotify:local:::Untitled:0"),
+            Ok(AnyId::Local(Cow::Borrowed("spotify:local:::Untitled:0")))
+        );
+
+        let invalid = [
+            (ID, IdError::InvalidPrefix),
+            (URI_SHORT, IdError::InvalidPrefix),
+            ("https://spotify.link/Ab3dEfGhIjK", IdError::ShortLink),
+            (URI_EMPTY, IdError::InvalidType),
+            (URI_WRONGTYPE1, IdError::InvalidType),
+            (
+                "spotify:collection:4iV5W9uYEdYUVa79Axb7Rh",
+                IdError::InvalidType,
+            ),
+            (URI_MIXED1, IdError::InvalidFormat),
+            (
+                "spotify:local:Daft+Punk:One+More+Time:320",
+                IdError::InvalidFormat,
+            ),
+            (
+                "spotify:local:Daft+Punk:Discovery:One+More+Time:x",
+                IdError::InvalidFormat,
+            ),
+            ("spotify:local:a:b:c:", IdError::InvalidFormat),
+            (
+                "spotify/local:Daft+Punk:Discovery:One+More+Time:320",
+                IdError::InvalidFormat,
+            ),
+            (
+                "spotify:user:spotify:album:37i9dQZF1DXcBWIGoYBM5M",
+                IdError::InvalidFormat,
+            ),
+            (
+                "spotify:user:spotify:playlist:37i9dQZF1DXc-WIGoYBM5M",
+                IdError::InvalidId,
+            ),
+            ("spotify:track:4iV5W9uYEdYU-a79Axb7Rh", IdError::InvalidId),
+        ];
+        for (s, error) in invalid {
+            assert_eq!(parsed(s), Err(error), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_any_id_conversions() {
+        let id: AnyId = URI.parse().unwrap();
+        assert_eq!(id._type(), Some(Type::Track));
+        assert_eq!(EpisodeId::try_from(id.clone()), Err(IdError::InvalidType));
+        assert!(PlayContextId::try_from(id.clone()).is_err());
+        assert!(matches!(
+            PlayableId::try_from(id.clone()),
+            Ok(PlayableId::Track(track)) if track.id() == ID
+        ));
+
+        let track = TrackId::try_from(id).unwrap();
+        assert_eq!(track.id(), ID);
+        assert_eq!(AnyId::from(track), URI.parse().unwrap());
+
+        let local: AnyId = "spotify:local:::Untitled:0".parse().unwrap();
+        assert_eq!(local._type(), None);
+        assert_eq!(TrackId::try_from(local), Err(IdError::InvalidType));
+    }
+
+    /// Both displaying and serializing return the URI, which is parsed back
+    /// into the same ID.
+    #[test]
+    fn test_any_id_round_trip() {
+        let uris = [
+            URI,
+            "spotify:user:wizzler",
+            "spotify:user:wizzler:collection",
+            "spotify:local:Daft+Punk:Discovery:One+More+Time:320",
+        ];
+        for uri in uris {
+            let id: AnyId = uri.parse().unwrap();
+            assert_eq!(id.to_string(), uri);
+
+            let json = serde_json::to_string(&id).unwrap();
+            assert_eq!(json, format!("\"{uri}\""));
+            assert_eq!(serde_json::from_str::<AnyId>(&json).unwrap(), id);
+        }
+
+        let legacy: AnyId = "spotify:user:spotify:playlist:37i9dQZF1DXcBWIGoYBM5M"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            legacy.to_string(),
+            "spotify:playlist:37i9dQZF1DXcBWIGoYBM5M"
+        );
+        assert!(serde_json::from_str::<AnyId>(&format!("\"{ID}\"")).is_err());
+    }
+
     /// Serializing should return the Id within it, not the URI.
     #[test]
     fn test_serialize() {
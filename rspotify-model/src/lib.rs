@@ -4,11 +4,14 @@
 pub mod album;
 pub mod artist;
 pub mod audio;
+pub mod audiobook;
 pub mod auth;
 pub mod category;
 pub mod context;
 pub(in crate) mod custom_serde;
 pub mod device;
+#[cfg(feature = "encrypted-cache")]
+pub mod encryption;
 pub mod enums;
 pub mod error;
 pub mod idtypes;
@@ -19,16 +22,20 @@ pub mod playing;
 pub mod playlist;
 pub mod recommend;
 pub mod search;
+pub mod search_query;
 pub mod show;
 pub mod track;
 pub mod user;
 
 pub use {
-    album::*, artist::*, audio::*, auth::*, category::*, context::*, device::*, enums::*, error::*,
-    idtypes::*, image::*, offset::*, page::*, playing::*, playlist::*, recommend::*, search::*,
-    show::*, track::*, user::*,
+    album::*, artist::*, audio::*, audiobook::*, auth::*, category::*, context::*, device::*,
+    enums::*, error::*, idtypes::*, image::*, offset::*, page::*, playing::*, playlist::*,
+    recommend::*, search::*, search_query::*, show::*, track::*, user::*,
 };
 
+#[cfg(feature = "encrypted-cache")]
+pub use encryption::*;
+
 use serde::{Deserialize, Serialize};
 
 /// Followers object
@@ -39,24 +46,27 @@ pub struct Followers {
     pub total: u32,
 }
 
-/// A full track object or a full episode object
+/// A full track object, a full episode object or a chapter object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum PlayableItem {
     Track(track::FullTrack),
     Episode(show::FullEpisode),
+    Chapter(audiobook::Chapter),
 }
 
 impl PlayableItem {
     /// Utility to get the ID from either variant in the enum.
     ///
     /// Note that if it's a track and if it's local, it may not have an ID, in
-    /// which case this function will return `None`.
+    /// which case this function will return `None`. Chapters can't be
+    /// referred to with a [`PlayableId`], so this returns `None` for them too.
     #[must_use]
     pub fn id(&self) -> Option<PlayableId<'_>> {
         match self {
             PlayableItem::Track(t) => t.id.as_ref().map(|t| PlayableId::Track(t.as_ref())),
             PlayableItem::Episode(e) => Some(PlayableId::Episode(e.id.as_ref())),
+            PlayableItem::Chapter(_) => None,
         }
     }
 }
@@ -27,8 +27,23 @@ pub struct CursorBasedPage<T> {
     pub total: Option<u32>,
 }
 
+/// The position of an automatic paginator, which can be saved in order to
+/// resume the pagination later on.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Checkpoint {
+    /// The offset of the next item, for offset-based pagination.
+    Offset(u32),
+    /// For cursor-based pagination, the cursor of the page with the next item
+    /// (`None` for the first page), and how many of its items have been
+    /// consumed already.
+    Cursor { cursor: Option<String>, skip: u32 },
+}
+
 /// Cursor object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct Cursor {
     pub after: Option<String>,
+    #[serde(default)]
+    pub before: Option<String>,
 }
@@ -3,7 +3,7 @@
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::{Context, FullTrack};
+use crate::{Context, FullTrack, Id, PlayableId, PlayableItem};
 
 /// Playing history object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -12,3 +12,38 @@ pub struct PlayHistory {
     pub played_at: DateTime<Utc>,
     pub context: Option<Context>,
 }
+
+/// The user's playback queue
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CurrentUserQueue {
+    pub currently_playing: Option<PlayableItem>,
+    pub queue: Vec<PlayableItem>,
+}
+
+impl CurrentUserQueue {
+    /// Returns the index in the queue of the first occurrence of an item,
+    /// without including the currently playing one.
+    #[must_use]
+    pub fn position(&self, id: &PlayableId<'_>) -> Option<usize> {
+        let uri = id.uri();
+        self.queue
+            .iter()
+            .position(|item| matches!(item.id(), Some(id) if id.uri() == uri))
+    }
+
+    /// Whether an item is in the queue, without including the currently
+    /// playing one.
+    #[must_use]
+    pub fn contains(&self, id: &PlayableId<'_>) -> bool {
+        self.position(id).is_some()
+    }
+
+    /// Whether an item is currently playing.
+    #[must_use]
+    pub fn is_playing(&self, id: &PlayableId<'_>) -> bool {
+        matches!(
+            self.currently_playing.as_ref().and_then(PlayableItem::id),
+            Some(item) if item.uri() == id.uri()
+        )
+    }
+}
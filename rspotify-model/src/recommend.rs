@@ -27,6 +27,12 @@ pub struct RecommendationsSeed {
     pub _type: RecommendationsSeedType,
 }
 
+/// Intermediate genre seeds wrapped by an object
+#[derive(Deserialize)]
+pub struct GenreSeedsPayload {
+    pub genres: Vec<String>,
+}
+
 /// The attributes for recommendations
 #[derive(Clone, Copy, Debug, Serialize, PartialEq, IntoStaticStr)]
 #[serde(rename_all = "snake_case")]
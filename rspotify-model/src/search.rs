@@ -1,12 +1,46 @@
 //! All object related to search
 
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::{
-    FullArtist, FullTrack, Page, SimplifiedAlbum, SimplifiedEpisode, SimplifiedPlaylist,
-    SimplifiedShow,
+    FullArtist, FullTrack, Page, SearchType, SimplifiedAlbum, SimplifiedAudiobook,
+    SimplifiedEpisode, SimplifiedPlaylist, SimplifiedShow,
 };
 
+/// The items that can be searched for, which determine the [`SearchType`] of
+/// a search from the type of its results.
+pub trait Searchable: DeserializeOwned {
+    const SEARCH_TYPE: SearchType;
+}
+
+impl Searchable for SimplifiedPlaylist {
+    const SEARCH_TYPE: SearchType = SearchType::Playlist;
+}
+
+impl Searchable for SimplifiedAlbum {
+    const SEARCH_TYPE: SearchType = SearchType::Album;
+}
+
+impl Searchable for FullArtist {
+    const SEARCH_TYPE: SearchType = SearchType::Artist;
+}
+
+impl Searchable for FullTrack {
+    const SEARCH_TYPE: SearchType = SearchType::Track;
+}
+
+impl Searchable for SimplifiedShow {
+    const SEARCH_TYPE: SearchType = SearchType::Show;
+}
+
+impl Searchable for SimplifiedEpisode {
+    const SEARCH_TYPE: SearchType = SearchType::Episode;
+}
+
+impl Searchable for SimplifiedAudiobook {
+    const SEARCH_TYPE: SearchType = SearchType::Audiobook;
+}
+
 /// Search for playlists
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SearchPlaylists {
@@ -44,6 +78,12 @@ pub struct SearchEpisodes {
     pub episodes: Page<SimplifiedEpisode>,
 }
 
+/// Search for audiobooks
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SearchAudiobooks {
+    pub audiobooks: Page<SimplifiedAudiobook>,
+}
+
 /// Search result of any kind
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum SearchResult {
@@ -59,4 +99,19 @@ pub enum SearchResult {
     Shows(Page<SimplifiedShow>),
     #[serde(rename = "episodes")]
     Episodes(Page<SimplifiedEpisode>),
+    #[serde(rename = "audiobooks")]
+    Audiobooks(Page<SimplifiedAudiobook>),
+}
+
+/// Search result of several kinds at once, where only the requested types are
+/// present. Each page can be paginated on its own by following its links.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct SearchMultipleResult {
+    pub playlists: Option<Page<SimplifiedPlaylist>>,
+    pub albums: Option<Page<SimplifiedAlbum>>,
+    pub artists: Option<Page<FullArtist>>,
+    pub tracks: Option<Page<FullTrack>>,
+    pub shows: Option<Page<SimplifiedShow>>,
+    pub episodes: Option<Page<SimplifiedEpisode>>,
+    pub audiobooks: Option<Page<SimplifiedAudiobook>>,
 }
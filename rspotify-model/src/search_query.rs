@@ -0,0 +1,276 @@
+//! Builder for the search queries, with support for field filters and
+//! operators.
+
+use std::{borrow::Cow, fmt, ops::RangeInclusive};
+
+/// A single condition of a [`SearchQuery`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SearchFilter {
+    /// Keywords matched against any field
+    Text(String),
+    Artist(String),
+    Album(String),
+    Track(String),
+    Year(u32),
+    /// An inclusive range of years
+    YearRange(u32, u32),
+    Genre(String),
+    Isrc(String),
+    Upc(String),
+    /// Albums released in the past two weeks. Only works for albums.
+    TagNew,
+    /// Albums with the lowest 10% popularity. Only works for albums.
+    TagHipster,
+}
+
+impl fmt::Display for SearchFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use SearchFilter::*;
+
+        match self {
+            Text(text) => write!(f, "{}", quote(text)),
+            Artist(artist) => write!(f, "artist:{}", quote(artist)),
+            Album(album) => write!(f, "album:{}", quote(album)),
+            Track(track) => write!(f, "track:{}", quote(track)),
+            Year(year) => write!(f, "year:{year}"),
+            YearRange(start, end) => write!(f, "year:{start}-{end}"),
+            Genre(genre) => write!(f, "genre:{}", quote(genre)),
+            Isrc(isrc) => write!(f, "isrc:{}", quote(isrc)),
+            Upc(upc) => write!(f, "upc:{}", quote(upc)),
+            TagNew => write!(f, "tag:new"),
+            TagHipster => write!(f, "tag:hipster"),
+        }
+    }
+}
+
+/// A term of the query, which all have to match.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Term {
+    Filter(SearchFilter),
+    Not(SearchFilter),
+    Or(Vec<SearchFilter>),
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Filter(filter) => write!(f, "{filter}"),
+            Term::Not(filter) => write!(f, "NOT {filter}"),
+            Term::Or(filters) => {
+                for (i, filter) in filters.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " OR ")?;
+                    }
+                    write!(f, "{filter}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Wraps the value in quotes if it would otherwise be misinterpreted, i.e.,
+/// if it contains whitespace, colons or quotes, or if it's an operator. The
+/// quotes and backslashes inside are escaped with a backslash.
+fn quote(value: &str) -> Cow<'_, str> {
+    let needs_quotes = value.is_empty()
+        || value.starts_with('-')
+        || matches!(value, "AND" | "OR" | "NOT")
+        || value
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, ':' | '"' | '\\'));
+    if !needs_quotes {
+        return Cow::Borrowed(value);
+    }
+
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        if matches!(c, '"' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    Cow::Owned(quoted)
+}
+
+/// The query of a search, which can be passed directly to the `search`
+/// endpoints of the clients. It's formatted with [`fmt::Display`], and the
+/// terms are separated with spaces so that all of them have to match.
+///
+/// ```
+/// use rspotify_model::{SearchFilter, SearchQuery};
+///
+/// let query = SearchQuery::new()
+///     .artist("Daft Punk")
+///     .year_range(2000..=2010)
+///     .not(SearchFilter::Album("Discovery".to_owned()))
+///     .or([SearchFilter::Genre("house".to_owned()), SearchFilter::TagNew]);
+/// assert_eq!(
+///     query.to_string(),
+///     r#"artist:"Daft Punk" year:2000-2010 NOT album:Discovery genre:house OR tag:new"#
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SearchQuery {
+    terms: Vec<Term>,
+}
+
+impl SearchQuery {
+    /// Creates an empty query.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a condition that has to match.
+    #[must_use]
+    pub fn filter(mut self, filter: SearchFilter) -> Self {
+        self.terms.push(Term::Filter(filter));
+        self
+    }
+
+    /// Adds a condition that must not match, with the `NOT` operator.
+    #[must_use]
+    pub fn not(mut self, filter: SearchFilter) -> Self {
+        self.terms.push(Term::Not(filter));
+        self
+    }
+
+    /// Adds a set of conditions of which at least one has to match, with the
+    /// `OR` operator.
+    #[must_use]
+    pub fn or(mut self, filters: impl IntoIterator<Item = SearchFilter>) -> Self {
+        let filters = filters.into_iter().collect::<Vec<_>>();
+        if !filters.is_empty() {
+            self.terms.push(Term::Or(filters));
+        }
+        self
+    }
+
+    /// Adds keywords matched against any field.
+    #[must_use]
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.filter(SearchFilter::Text(text.into()))
+    }
+
+    #[must_use]
+    pub fn artist(self, artist: impl Into<String>) -> Self {
+        self.filter(SearchFilter::Artist(artist.into()))
+    }
+
+    #[must_use]
+    pub fn album(self, album: impl Into<String>) -> Self {
+        self.filter(SearchFilter::Album(album.into()))
+    }
+
+    #[must_use]
+    pub fn track(self, track: impl Into<String>) -> Self {
+        self.filter(SearchFilter::Track(track.into()))
+    }
+
+    #[must_use]
+    pub fn year(self, year: u32) -> Self {
+        self.filter(SearchFilter::Year(year))
+    }
+
+    #[must_use]
+    pub fn year_range(self, years: RangeInclusive<u32>) -> Self {
+        self.filter(SearchFilter::YearRange(*years.start(), *years.end()))
+    }
+
+    #[must_use]
+    pub fn genre(self, genre: impl Into<String>) -> Self {
+        self.filter(SearchFilter::Genre(genre.into()))
+    }
+
+    #[must_use]
+    pub fn isrc(self, isrc: impl Into<String>) -> Self {
+        self.filter(SearchFilter::Isrc(isrc.into()))
+    }
+
+    #[must_use]
+    pub fn upc(self, upc: impl Into<String>) -> Self {
+        self.filter(SearchFilter::Upc(upc.into()))
+    }
+
+    /// Only matches albums released in the past two weeks.
+    #[must_use]
+    pub fn tag_new(self) -> Self {
+        self.filter(SearchFilter::TagNew)
+    }
+
+    /// Only matches albums with the lowest 10% popularity.
+    #[must_use]
+    pub fn tag_hipster(self) -> Self {
+        self.filter(SearchFilter::TagHipster)
+    }
+}
+
+impl fmt::Display for SearchQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, term) in self.terms.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{term}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("abba"), "abba");
+        assert_eq!(quote("99%"), "99%");
+        assert_eq!(quote("tania bowra"), r#""tania bowra""#);
+        assert_eq!(quote("a:b"), r#""a:b""#);
+        assert_eq!(quote(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(quote(r"back\slash"), r#""back\\slash""#);
+        assert_eq!(quote("OR"), r#""OR""#);
+        assert_eq!(quote("-minus"), r#""-minus""#);
+        assert_eq!(quote(""), r#""""#);
+    }
+
+    #[test]
+    fn test_search_query() {
+        assert_eq!(SearchQuery::new().to_string(), "");
+
+        let query = SearchQuery::new()
+            .text("arrival")
+            .artist("abba")
+            .year(1976)
+            .isrc("SEAYD7601020")
+            .upc("00602547202307");
+        assert_eq!(
+            query.to_string(),
+            "arrival artist:abba year:1976 isrc:SEAYD7601020 upc:00602547202307"
+        );
+
+        let query = SearchQuery::new()
+            .track("Dancing Queen")
+            .not(SearchFilter::Genre("hip hop".to_owned()))
+            .or([])
+            .tag_new()
+            .tag_hipster();
+        assert_eq!(
+            query.to_string(),
+            r#"track:"Dancing Queen" NOT genre:"hip hop" tag:new tag:hipster"#
+        );
+
+        let query = SearchQuery::new().or([
+            SearchFilter::Album("Arrival".to_owned()),
+            SearchFilter::Album("Voulez-Vous".to_owned()),
+            SearchFilter::YearRange(1970, 1979),
+        ]);
+        assert_eq!(
+            query.to_string(),
+            "album:Arrival OR album:Voulez-Vous OR year:1970-1979"
+        );
+    }
+}
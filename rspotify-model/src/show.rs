@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use std::collections::HashMap;
@@ -116,6 +117,13 @@ pub struct FullEpisode {
     pub show: SimplifiedShow,
 }
 
+/// Saved episode object
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SavedEpisode {
+    pub added_at: DateTime<Utc>,
+    pub episode: FullEpisode,
+}
+
 /// Intermediate episodes feature object wrapped by `Vec`
 #[derive(Deserialize)]
 pub struct EpisodesPayload {
@@ -1,7 +1,8 @@
 use crate::{
-    auth_urls,
-    clients::{BaseClient, OAuthClient},
-    http::{Form, HttpClient},
+    alphabets, auth_urls,
+    clients::{parse_token, BaseClient, OAuthClient},
+    generate_random_string,
+    http::{BaseHttpClient, Form, HttpClient},
     join_scopes, params,
     sync::Mutex,
     ClientResult, Config, Credentials, OAuth, Token,
@@ -53,6 +54,11 @@ use url::Url;
 ///
 /// ![demo](https://raw.githubusercontent.com/ramsayleung/rspotify/master/doc/images/rspotify.gif)
 ///
+/// If your application can't include the client secret, you can set up a
+/// token swap service and configure it with [`Config::token_swap`]. The code
+/// exchange and the token refreshing will then be delegated to it, and only
+/// the client ID will be needed in the credentials.
+///
 /// Note: even if your script does not have an accessible URL, you will have to
 /// specify a redirect URI. It doesn't need to work, you can use
 /// `http://localhost:8888/callback` for example, which will also have the code
@@ -92,6 +98,9 @@ impl BaseClient for AuthCodeSpotify {
 
     /// Refetch the current access token given a refresh token. May return
     /// `None` if there's no access/refresh token.
+    ///
+    /// If a [`TokenSwap`](crate::TokenSwap) is configured, its refresh URL
+    /// will be used instead of Spotify's token endpoint.
     async fn refetch_token(&self) -> ClientResult<Option<Token>> {
         match self.token.lock().await.unwrap().as_ref() {
             Some(Token {
@@ -100,13 +109,19 @@ impl BaseClient for AuthCodeSpotify {
             }) => {
                 let mut data = Form::new();
                 data.insert(params::REFRESH_TOKEN, refresh_token);
-                data.insert(params::GRANT_TYPE, params::REFRESH_TOKEN);
 
-                let headers = self
-                    .creds
-                    .auth_headers()
-                    .expect("No client secret set in the credentials.");
-                let mut token = self.fetch_access_token(&data, Some(&headers)).await?;
+                let mut token = match &self.config.token_swap {
+                    Some(swap) => self.fetch_swapped_token(&swap.refresh_url, &data).await?,
+                    None => {
+                        data.insert(params::GRANT_TYPE, params::REFRESH_TOKEN);
+
+                        let headers = self
+                            .creds
+                            .auth_headers()
+                            .expect("No client secret set in the credentials.");
+                        self.fetch_access_token(&data, Some(&headers)).await?
+                    }
+                };
                 token.refresh_token = Some(refresh_token.to_string());
                 Ok(Some(token))
             }
@@ -125,28 +140,55 @@ impl OAuthClient for AuthCodeSpotify {
 
     /// Obtains a user access token given a code, as part of the OAuth
     /// authentication. The access token will be saved internally.
+    ///
+    /// If a [`TokenSwap`](crate::TokenSwap) is configured, the code will be
+    /// exchanged by its swap URL, which doesn't require the client secret.
     async fn request_token(&self, code: &str) -> ClientResult<()> {
         log::info!("Requesting Auth Code token");
 
-        let scopes = join_scopes(&self.oauth.scopes);
+        let token = match &self.config.token_swap {
+            Some(swap) => {
+                let mut data = Form::new();
+                data.insert(params::CODE, code);
+
+                self.fetch_swapped_token(&swap.swap_url, &data).await?
+            }
+            None => {
+                let scopes = join_scopes(&self.oauth.scopes);
 
-        let mut data = Form::new();
-        data.insert(params::GRANT_TYPE, params::GRANT_TYPE_AUTH_CODE);
-        data.insert(params::REDIRECT_URI, &self.oauth.redirect_uri);
-        data.insert(params::CODE, code);
-        data.insert(params::SCOPE, &scopes);
-        data.insert(params::STATE, &self.oauth.state);
+                let mut data = Form::new();
+                data.insert(params::GRANT_TYPE, params::GRANT_TYPE_AUTH_CODE);
+                data.insert(params::REDIRECT_URI, &self.oauth.redirect_uri);
+                data.insert(params::CODE, code);
+                data.insert(params::SCOPE, &scopes);
+                data.insert(params::STATE, &self.oauth.state);
 
-        let headers = self
-            .creds
-            .auth_headers()
-            .expect("No client secret set in the credentials.");
+                let headers = self
+                    .creds
+                    .auth_headers()
+                    .expect("No client secret set in the credentials.");
 
-        let token = self.fetch_access_token(&data, Some(&headers)).await?;
+                self.fetch_access_token(&data, Some(&headers)).await?
+            }
+        };
         *self.token.lock().await.unwrap() = Some(token);
 
         self.write_token_cache().await
     }
+
+    /// Signs the user out, also regenerating the OAuth state. See
+    /// [`OAuthClient::logout`] for more information.
+    async fn logout(&mut self) -> ClientResult<()> {
+        log::info!("Logging out");
+
+        *self.token.lock().await.unwrap() = None;
+        self.oauth.state = generate_random_string(16, alphabets::ALPHANUM);
+        if self.config.token_cached {
+            self.config.remove_cache()?;
+        }
+
+        Ok(())
+    }
 }
 
 impl AuthCodeSpotify {
@@ -177,6 +219,7 @@ impl AuthCodeSpotify {
     #[must_use]
     pub fn with_config(creds: Credentials, oauth: OAuth, config: Config) -> Self {
         Self {
+            http: config.http_client(),
             creds,
             oauth,
             config,
@@ -205,4 +248,14 @@ impl AuthCodeSpotify {
         let parsed = Url::parse_with_params(auth_urls::AUTHORIZE, payload)?;
         Ok(parsed.into())
     }
+
+    /// Sends a request to the token swap service, which responds with a token
+    /// just like Spotify's token endpoint would.
+    #[maybe_async]
+    async fn fetch_swapped_token(&self, url: &str, payload: &Form<'_>) -> ClientResult<Token> {
+        let (response, response_headers) =
+            self.http.post_form_with_headers(url, None, payload).await?;
+
+        parse_token(&response, &response_headers)
+    }
 }
@@ -112,6 +112,21 @@ impl OAuthClient for AuthCodePkceSpotify {
 
         self.write_token_cache().await
     }
+
+    /// Signs the user out, also resetting the code verifier. See
+    /// [`OAuthClient::logout`] for more information.
+    async fn logout(&mut self) -> ClientResult<()> {
+        log::info!("Logging out");
+
+        *self.token.lock().await.unwrap() = None;
+        self.oauth.state = generate_random_string(16, alphabets::ALPHANUM);
+        self.verifier = None;
+        if self.config.token_cached {
+            self.config.remove_cache()?;
+        }
+
+        Ok(())
+    }
 }
 
 impl AuthCodePkceSpotify {
@@ -142,6 +157,7 @@ impl AuthCodePkceSpotify {
     #[must_use]
     pub fn with_config(creds: Credentials, oauth: OAuth, config: Config) -> Self {
         Self {
+            http: config.http_client(),
             creds,
             oauth,
             config,
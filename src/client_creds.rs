@@ -1,5 +1,5 @@
 use crate::{
-    clients::BaseClient,
+    clients::{is_token_expired, BaseClient},
     http::{Form, HttpClient},
     params,
     sync::Mutex,
@@ -84,6 +84,7 @@ impl ClientCredsSpotify {
     #[must_use]
     pub fn with_config(creds: Credentials, config: Config) -> Self {
         Self {
+            http: config.http_client(),
             config,
             creds,
             ..Default::default()
@@ -105,8 +106,8 @@ impl ClientCredsSpotify {
         }
 
         log::info!("Reading token cache");
-        let token = Token::from_cache(&self.get_config().cache_path)?;
-        if token.is_expired() {
+        let token = self.get_config().read_cache()?;
+        if is_token_expired(&token, &self.config) {
             // Invalid token, since it's expired.
             Ok(None)
         } else {
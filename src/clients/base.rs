@@ -1,11 +1,12 @@
 use crate::{
     auth_urls,
     clients::{
-        convert_result,
-        pagination::{paginate, Paginator},
+        convert_page, convert_result, is_token_expired,
+        pagination::{paginate_concurrent, paginate_pages, Paginator},
+        parse_token, request_chunks, within_search_window, ApiRequest, Method, SEARCH_WINDOW,
     },
     http::{BaseHttpClient, Form, Headers, HttpClient, Query},
-    join_ids,
+    join_ids, join_ids_chunked,
     model::*,
     sync::Mutex,
     util::build_map,
@@ -14,9 +15,13 @@ use crate::{
 
 use std::{collections::HashMap, fmt, sync::Arc};
 
-use chrono::Utc;
+#[cfg(feature = "__async")]
+use std::future::Future;
+
 use maybe_async::maybe_async;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
+use url::Url;
 
 /// This trait implements the basic endpoints from the Spotify API that may be
 /// accessed without user authorization, including parts of the authentication
@@ -64,7 +69,7 @@ where
             .await
             .unwrap()
             .as_ref()
-            .map_or(false, Token::is_expired);
+            .map_or(false, |tok| is_token_expired(tok, self.get_config()));
 
         if should_reauth {
             self.refresh_token().await
@@ -105,14 +110,14 @@ where
     //
     // The Spotify client has two different wrappers to perform requests:
     //
-    // * Basic wrappers: `get`, `post`, `put`, `delete`, `post_form`. These only
-    //   append the configured Spotify API URL to the relative URL provided so
-    //   that it's not forgotten. They're used in the authentication process to
-    //   request an access token and similars.
+    // * Basic wrappers: `get`, `post`, `put`, `put_raw`, `delete`,
+    //   `post_form`. These only append the configured Spotify API URL to the
+    //   relative URL provided so that it's not forgotten. They're used in the
+    //   authentication process to request an access token and similars.
     // * Endpoint wrappers: `endpoint_get`, `endpoint_post`, `endpoint_put`,
-    //   `endpoint_delete`. These append the authentication headers for endpoint
-    //   requests to reduce the code needed for endpoints and make them as
-    //   concise as possible.
+    //   `endpoint_put_raw`, `endpoint_delete`. These append the authentication
+    //   headers for endpoint requests to reduce the code needed for endpoints
+    //   and make them as concise as possible.
 
     #[doc(hidden)]
     #[inline]
@@ -162,6 +167,22 @@ where
         Ok(self.get_http().put(&url, headers, payload).await?)
     }
 
+    #[doc(hidden)]
+    #[inline]
+    async fn put_raw(
+        &self,
+        url: &str,
+        headers: Option<&Headers>,
+        body: &[u8],
+        content_type: &str,
+    ) -> ClientResult<String> {
+        let url = self.endpoint_url(url);
+        Ok(self
+            .get_http()
+            .put_raw(&url, headers, body, content_type)
+            .await?)
+    }
+
     #[doc(hidden)]
     #[inline]
     async fn delete(
@@ -198,6 +219,18 @@ where
         self.put(url, Some(&headers), payload).await
     }
 
+    #[doc(hidden)]
+    #[inline]
+    async fn endpoint_put_raw(
+        &self,
+        url: &str,
+        body: &[u8],
+        content_type: &str,
+    ) -> ClientResult<String> {
+        let headers = self.auth_headers().await;
+        self.put_raw(url, Some(&headers), body, content_type).await
+    }
+
     #[doc(hidden)]
     #[inline]
     async fn endpoint_delete(&self, url: &str, payload: &Value) -> ClientResult<String> {
@@ -205,6 +238,17 @@ where
         self.delete(url, Some(&headers), payload).await
     }
 
+    #[doc(hidden)]
+    async fn endpoint_request(&self, request: &ApiRequest) -> ClientResult<String> {
+        let url = Url::parse_with_params(&self.endpoint_url(&request.path), &request.query)?;
+        match request.method {
+            Method::Get => self.endpoint_get(url.as_str(), &Query::new()).await,
+            Method::Post => self.endpoint_post(url.as_str(), &request.body).await,
+            Method::Put => self.endpoint_put(url.as_str(), &request.body).await,
+            Method::Delete => self.endpoint_delete(url.as_str(), &request.body).await,
+        }
+    }
+
     /// Updates the cache file at the internal cache path.
     ///
     /// This should be used whenever it's possible to, even if the cached token
@@ -218,7 +262,7 @@ where
 
         log::info!("Writing token cache");
         if let Some(tok) = self.get_token().lock().await.unwrap().as_ref() {
-            tok.write_cache(&self.get_config().cache_path)?;
+            self.get_config().write_cache(tok)?;
         }
 
         Ok(())
@@ -230,11 +274,12 @@ where
         payload: &Form<'_>,
         headers: Option<&Headers>,
     ) -> ClientResult<Token> {
-        let response = self.post_form(auth_urls::TOKEN, headers, payload).await?;
+        let (response, response_headers) = self
+            .get_http()
+            .post_form_with_headers(auth_urls::TOKEN, headers, payload)
+            .await?;
 
-        let mut tok = serde_json::from_str::<Token>(&response)?;
-        tok.expires_at = Utc::now().checked_add_signed(tok.expires_in);
-        Ok(tok)
+        parse_token(&response, &response_headers)
     }
 
     /// Returns a single track given the track's ID, URI or URL.
@@ -252,7 +297,8 @@ where
     /// Returns a list of tracks given a list of track IDs, URIs, or URLs.
     ///
     /// Parameters:
-    /// - track_ids - a list of spotify URIs, URLs or IDs
+    /// - track_ids - a list of spotify URIs, URLs or IDs. They're requested
+    ///   in chunks of 50.
     /// - market - an ISO 3166-1 alpha-2 country code or the string from_token.
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-several-tracks)
@@ -261,12 +307,20 @@ where
         track_ids: impl IntoIterator<Item = TrackId<'a>> + Send + 'a,
         market: Option<Market>,
     ) -> ClientResult<Vec<FullTrack>> {
-        let ids = join_ids(track_ids);
-        let params = build_map([("market", market.map(Into::into))]);
-
-        let url = format!("tracks/?ids={ids}");
-        let result = self.endpoint_get(&url, &params).await?;
-        convert_result::<FullTracks>(&result).map(|x| x.tracks)
+        let chunks = join_ids_chunked(track_ids, 50);
+        let params = &build_map([("market", market.map(Into::into))]);
+
+        let tracks = request_chunks(
+            chunks,
+            self.get_config().chunk_concurrency,
+            |ids| async move {
+                let url = format!("tracks/?ids={ids}");
+                let result = self.endpoint_get(&url, params).await?;
+                convert_result::<FullTracks>(&result).map(|x| x.tracks)
+            },
+        )
+        .await?;
+        Ok(tracks.into_iter().flatten().collect())
     }
 
     /// Returns a single artist given the artist's ID, URI or URL.
@@ -284,18 +338,26 @@ where
     /// Returns a list of artists given the artist IDs, URIs, or URLs.
     ///
     /// Parameters:
-    /// - artist_ids - a list of artist IDs, URIs or URLs
+    /// - artist_ids - a list of artist IDs, URIs or URLs. They're requested in
+    ///   chunks of 50.
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-multiple-artists)
     async fn artists<'a>(
         &self,
         artist_ids: impl IntoIterator<Item = ArtistId<'a>> + Send + 'a,
     ) -> ClientResult<Vec<FullArtist>> {
-        let ids = join_ids(artist_ids);
-        let url = format!("artists/?ids={ids}");
-        let result = self.endpoint_get(&url, &Query::new()).await?;
-
-        convert_result::<FullArtists>(&result).map(|x| x.artists)
+        let chunks = join_ids_chunked(artist_ids, 50);
+        let artists = request_chunks(
+            chunks,
+            self.get_config().chunk_concurrency,
+            |ids| async move {
+                let url = format!("artists/?ids={ids}");
+                let result = self.endpoint_get(&url, &Query::new()).await?;
+                convert_result::<FullArtists>(&result).map(|x| x.artists)
+            },
+        )
+        .await?;
+        Ok(artists.into_iter().flatten().collect())
     }
 
     /// Get Spotify catalog information about an artist's albums.
@@ -317,11 +379,44 @@ where
         album_type: Option<AlbumType>,
         market: Option<Market>,
     ) -> Paginator<'_, ClientResult<SimplifiedAlbum>> {
-        paginate(
+        paginate_concurrent(
             move |limit, offset| {
                 self.artist_albums_manual(artist_id, album_type, market, Some(limit), Some(offset))
             },
             self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::artist_albums`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn artist_albums_owned(
+        &self,
+        artist_id: ArtistId<'static>,
+        album_type: Option<AlbumType>,
+        market: Option<Market>,
+    ) -> Paginator<'static, ClientResult<SimplifiedAlbum>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let (client, artist_id) = (Arc::clone(&client), artist_id.clone());
+                async move {
+                    client
+                        .artist_albums_manual(
+                            &artist_id,
+                            album_type,
+                            market,
+                            Some(limit),
+                            Some(offset),
+                        )
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
         )
     }
 
@@ -401,24 +496,33 @@ where
     /// Returns a list of albums given the album IDs, URIs, or URLs.
     ///
     /// Parameters:
-    /// - albums_ids - a list of album IDs, URIs or URLs
+    /// - albums_ids - a list of album IDs, URIs or URLs. They're requested in
+    ///   chunks of 20.
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-multiple-albums)
     async fn albums<'a>(
         &self,
         album_ids: impl IntoIterator<Item = AlbumId<'a>> + Send + 'a,
     ) -> ClientResult<Vec<FullAlbum>> {
-        let ids = join_ids(album_ids);
-        let url = format!("albums/?ids={ids}");
-        let result = self.endpoint_get(&url, &Query::new()).await?;
-        convert_result::<FullAlbums>(&result).map(|x| x.albums)
+        let chunks = join_ids_chunked(album_ids, 20);
+        let albums = request_chunks(
+            chunks,
+            self.get_config().chunk_concurrency,
+            |ids| async move {
+                let url = format!("albums/?ids={ids}");
+                let result = self.endpoint_get(&url, &Query::new()).await?;
+                convert_result::<FullAlbums>(&result).map(|x| x.albums)
+            },
+        )
+        .await?;
+        Ok(albums.into_iter().flatten().collect())
     }
 
     /// Search for an Item. Get Spotify catalog information about artists,
     /// albums, tracks or playlists that match a keyword string.
     ///
     /// Parameters:
-    /// - q - the search query
+    /// - q - the search query, which may be a [`SearchQuery`]
     /// - limit  - the number of items to return
     /// - offset - the index of the first item to return
     /// - type - the type of item to return. One of 'artist', 'album', 'track',
@@ -431,17 +535,18 @@ where
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/search)
     async fn search(
         &self,
-        q: &str,
+        q: impl fmt::Display + Send,
         _type: SearchType,
         market: Option<Market>,
         include_external: Option<IncludeExternal>,
         limit: Option<u32>,
         offset: Option<u32>,
     ) -> ClientResult<SearchResult> {
+        let q = q.to_string();
         let limit = limit.map(|s| s.to_string());
         let offset = offset.map(|s| s.to_string());
         let params = build_map([
-            ("q", Some(q)),
+            ("q", Some(q.as_str())),
             ("type", Some(_type.into())),
             ("market", market.map(Into::into)),
             ("include_external", include_external.map(Into::into)),
@@ -453,6 +558,176 @@ where
         convert_result(&result)
     }
 
+    /// Search for items of several types at once. The result contains a page
+    /// for each of the requested types, which may be paginated on its own with
+    /// [`Self::next_page`] and [`Self::previous_page`].
+    ///
+    /// Parameters:
+    /// - q - the search query, which may be a [`SearchQuery`]
+    /// - types - the types of item to return
+    /// - market - An ISO 3166-1 alpha-2 country code or the string from_token.
+    /// - include_external: Optional.Possible values: audio. If
+    ///   include_external=audio is specified the response will include any
+    ///   relevant audio content that is hosted externally.
+    /// - limit - the number of items to return of each type
+    /// - offset - the index of the first item to return of each type
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/search)
+    async fn search_multiple<'a>(
+        &self,
+        q: impl fmt::Display + Send + 'a,
+        types: impl IntoIterator<Item = SearchType> + Send + 'a,
+        market: Option<Market>,
+        include_external: Option<IncludeExternal>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> ClientResult<SearchMultipleResult> {
+        let q = q.to_string();
+        let types = types
+            .into_iter()
+            .map(<&str>::from)
+            .collect::<Vec<_>>()
+            .join(",");
+        let limit = limit.map(|s| s.to_string());
+        let offset = offset.map(|s| s.to_string());
+        let params = build_map([
+            ("q", Some(q.as_str())),
+            ("type", Some(types.as_str())),
+            ("market", market.map(Into::into)),
+            ("include_external", include_external.map(Into::into)),
+            ("limit", limit.as_deref()),
+            ("offset", offset.as_deref()),
+        ]);
+
+        let result = self.endpoint_get("search", &params).await?;
+        convert_result(&result)
+    }
+
+    /// Search for items of a single type, which is inferred from the type of
+    /// the results. For example, searching for [`FullTrack`] yields tracks.
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "__async")]
+    /// # async fn search(spotify: rspotify::ClientCredsSpotify) {
+    /// use futures::stream::TryStreamExt;
+    /// use rspotify::{model::FullTrack, prelude::*};
+    ///
+    /// let tracks = spotify
+    ///     .search_items::<FullTrack>("abba", None, None)
+    ///     .try_collect::<Vec<_>>()
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    ///
+    /// Parameters:
+    /// - q - the search query, which may be a [`SearchQuery`]
+    /// - market - An ISO 3166-1 alpha-2 country code or the string from_token.
+    /// - include_external: Optional.Possible values: audio. If
+    ///   include_external=audio is specified the response will include any
+    ///   relevant audio content that is hosted externally.
+    ///
+    /// Spotify only serves the first 1000 results of a search, so the
+    /// pagination ends there even if the `total` of the results is larger.
+    ///
+    /// See [`Self::search_items_manual`] for a manually paginated version of
+    /// this.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/search)
+    fn search_items<'a, T>(
+        &'a self,
+        q: impl fmt::Display,
+        market: Option<Market>,
+        include_external: Option<IncludeExternal>,
+    ) -> Paginator<'_, ClientResult<T>>
+    where
+        T: 'a + Searchable + Unpin + Send,
+    {
+        let q = q.to_string();
+        paginate_concurrent(
+            move |limit, offset| {
+                let q = q.clone();
+                async move {
+                    let limit = limit.min(SEARCH_WINDOW.saturating_sub(offset));
+                    self.search_items_manual(
+                        &q,
+                        market,
+                        include_external,
+                        Some(limit),
+                        Some(offset),
+                    )
+                    .await
+                    .map(within_search_window)
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::search_items`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn search_items_owned<T>(
+        &self,
+        q: impl fmt::Display,
+        market: Option<Market>,
+        include_external: Option<IncludeExternal>,
+    ) -> Paginator<'static, ClientResult<T>>
+    where
+        Self: 'static,
+        T: 'static + Searchable + Unpin + Send,
+    {
+        let client = Arc::new(self.clone());
+        let q = q.to_string();
+        paginate_concurrent(
+            move |limit, offset| {
+                let (client, q) = (Arc::clone(&client), q.clone());
+                async move {
+                    let limit = limit.min(SEARCH_WINDOW.saturating_sub(offset));
+                    client
+                        .search_items_manual(
+                            &q,
+                            market,
+                            include_external,
+                            Some(limit),
+                            Some(offset),
+                        )
+                        .await
+                        .map(within_search_window)
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The manually paginated version of [`Self::search_items`].
+    async fn search_items_manual<T: Searchable + Send>(
+        &self,
+        q: impl fmt::Display + Send,
+        market: Option<Market>,
+        include_external: Option<IncludeExternal>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> ClientResult<Page<T>> {
+        let q = q.to_string();
+        let limit = limit.map(|s| s.to_string());
+        let offset = offset.map(|s| s.to_string());
+        let params = build_map([
+            ("q", Some(q.as_str())),
+            ("type", Some(T::SEARCH_TYPE.into())),
+            ("market", market.map(Into::into)),
+            ("include_external", include_external.map(Into::into)),
+            ("limit", limit.as_deref()),
+            ("offset", offset.as_deref()),
+        ]);
+
+        // The page is nested inside a wrapper object, which only contains the
+        // requested type.
+        let result = self.endpoint_get("search", &params).await?;
+        convert_page(&result, "")
+    }
+
     /// Get Spotify catalog information about an album's tracks.
     ///
     /// Parameters:
@@ -468,9 +743,34 @@ where
         &'a self,
         album_id: &'a AlbumId<'_>,
     ) -> Paginator<'_, ClientResult<SimplifiedTrack>> {
-        paginate(
+        paginate_concurrent(
             move |limit, offset| self.album_track_manual(album_id, Some(limit), Some(offset)),
             self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::album_track`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn album_track_owned(
+        &self,
+        album_id: AlbumId<'static>,
+    ) -> Paginator<'static, ClientResult<SimplifiedTrack>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let (client, album_id) = (Arc::clone(&client), album_id.clone());
+                async move {
+                    client
+                        .album_track_manual(&album_id, Some(limit), Some(offset))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
         )
     }
 
@@ -522,6 +822,18 @@ where
         convert_result(&result)
     }
 
+    /// Gets the current cover images of a playlist.
+    ///
+    /// Parameters:
+    /// - playlist_id - the id of the playlist
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-playlist-cover)
+    async fn playlist_cover_image(&self, playlist_id: PlaylistId<'_>) -> ClientResult<Vec<Image>> {
+        let url = format!("playlists/{}/images", playlist_id.id());
+        let result = self.endpoint_get(&url, &Query::new()).await?;
+        convert_result(&result)
+    }
+
     /// Gets playlist of a user.
     ///
     /// Parameters:
@@ -629,11 +941,37 @@ where
         id: &'a ShowId<'_>,
         market: Option<Market>,
     ) -> Paginator<'_, ClientResult<SimplifiedEpisode>> {
-        paginate(
+        paginate_concurrent(
             move |limit, offset| {
                 self.get_shows_episodes_manual(id, market, Some(limit), Some(offset))
             },
             self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::get_shows_episodes`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn get_shows_episodes_owned(
+        &self,
+        id: ShowId<'static>,
+        market: Option<Market>,
+    ) -> Paginator<'static, ClientResult<SimplifiedEpisode>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let (client, id) = (Arc::clone(&client), id.clone());
+                async move {
+                    client
+                        .get_shows_episodes_manual(&id, market, Some(limit), Some(offset))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
         )
     }
 
@@ -682,7 +1020,8 @@ where
     /// Get Spotify catalog information for multiple episodes based on their Spotify IDs.
     ///
     /// Query Parameters
-    /// - ids: Required. A comma-separated list of the Spotify IDs for the episodes. Maximum: 50 IDs.
+    /// - ids: Required. A list of the Spotify IDs for the episodes, which are
+    ///   requested in chunks of 50.
     /// - market: Optional. An ISO 3166-1 alpha-2 country code or the string from_token.
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-multiple-episodes)
@@ -691,11 +1030,182 @@ where
         ids: impl IntoIterator<Item = EpisodeId<'a>> + Send + 'a,
         market: Option<Market>,
     ) -> ClientResult<Vec<FullEpisode>> {
-        let ids = join_ids(ids);
-        let params = build_map([("ids", Some(&ids)), ("market", market.map(Into::into))]);
+        let chunks = join_ids_chunked(ids, 50);
+        let episodes = request_chunks(
+            chunks,
+            self.get_config().chunk_concurrency,
+            |ids| async move {
+                let params = build_map([("ids", Some(&ids)), ("market", market.map(Into::into))]);
+                let result = self.endpoint_get("episodes", &params).await?;
+                convert_result::<EpisodesPayload>(&result).map(|x| x.episodes)
+            },
+        )
+        .await?;
+        Ok(episodes.into_iter().flatten().collect())
+    }
+
+    /// Get Spotify catalog information for a single audiobook.
+    ///
+    /// Parameters:
+    /// - id - the audiobook ID, URI or URL
+    /// - market - an ISO 3166-1 alpha-2 country code or the string from_token.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-an-audiobook)
+    async fn get_an_audiobook(
+        &self,
+        id: AudiobookId<'_>,
+        market: Option<Market>,
+    ) -> ClientResult<FullAudiobook> {
+        let params = build_map([("market", market.map(Into::into))]);
+
+        let url = format!("audiobooks/{}", id.id());
+        let result = self.endpoint_get(&url, &params).await?;
+        convert_result(&result)
+    }
+
+    /// Get Spotify catalog information for several audiobooks.
+    ///
+    /// Parameters:
+    /// - ids - a list of audiobook IDs, URIs or URLs, which are requested in
+    ///   chunks of 50.
+    /// - market - an ISO 3166-1 alpha-2 country code or the string from_token.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-multiple-audiobooks)
+    async fn get_several_audiobooks<'a>(
+        &self,
+        ids: impl IntoIterator<Item = AudiobookId<'a>> + Send + 'a,
+        market: Option<Market>,
+    ) -> ClientResult<Vec<FullAudiobook>> {
+        let chunks = join_ids_chunked(ids, 50);
+        let audiobooks = request_chunks(
+            chunks,
+            self.get_config().chunk_concurrency,
+            |ids| async move {
+                let params = build_map([("ids", Some(&ids)), ("market", market.map(Into::into))]);
+                let result = self.endpoint_get("audiobooks", &params).await?;
+                convert_result::<AudiobooksPayload>(&result).map(|x| x.audiobooks)
+            },
+        )
+        .await?;
+        Ok(audiobooks.into_iter().flatten().collect())
+    }
+
+    /// Get Spotify catalog information about an audiobook's chapters.
+    ///
+    /// Parameters:
+    /// - id - the audiobook ID, URI or URL
+    /// - market - an ISO 3166-1 alpha-2 country code or the string from_token.
+    /// - limit - the number of chapters to return
+    /// - offset - the index of the first chapter to return
+    ///
+    /// See [`Self::get_audiobook_chapters_manual`] for a manually paginated
+    /// version of this.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-audiobook-chapters)
+    fn get_audiobook_chapters<'a>(
+        &'a self,
+        id: &'a AudiobookId<'_>,
+        market: Option<Market>,
+    ) -> Paginator<'_, ClientResult<Chapter>> {
+        paginate_concurrent(
+            move |limit, offset| {
+                self.get_audiobook_chapters_manual(id, market, Some(limit), Some(offset))
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::get_audiobook_chapters`], which borrows
+    /// nothing so that it can be moved to other tasks or threads.
+    fn get_audiobook_chapters_owned(
+        &self,
+        id: AudiobookId<'static>,
+        market: Option<Market>,
+    ) -> Paginator<'static, ClientResult<Chapter>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let (client, id) = (Arc::clone(&client), id.clone());
+                async move {
+                    client
+                        .get_audiobook_chapters_manual(&id, market, Some(limit), Some(offset))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The manually paginated version of [`Self::get_audiobook_chapters`].
+    async fn get_audiobook_chapters_manual(
+        &self,
+        id: &AudiobookId<'_>,
+        market: Option<Market>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> ClientResult<Page<Chapter>> {
+        let limit = limit.map(|x| x.to_string());
+        let offset = offset.map(|x| x.to_string());
+        let params = build_map([
+            ("market", market.map(Into::into)),
+            ("limit", limit.as_deref()),
+            ("offset", offset.as_deref()),
+        ]);
+
+        let url = format!("audiobooks/{}/chapters", id.id());
+        let result = self.endpoint_get(&url, &params).await?;
+        convert_result(&result)
+    }
 
-        let result = self.endpoint_get("episodes", &params).await?;
-        convert_result::<EpisodesPayload>(&result).map(|x| x.episodes)
+    /// Get Spotify catalog information for a single chapter of an audiobook.
+    ///
+    /// Parameters:
+    /// - id - the chapter ID, URI or URL
+    /// - market - an ISO 3166-1 alpha-2 country code or the string from_token.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-a-chapter)
+    async fn get_a_chapter(
+        &self,
+        id: ChapterId<'_>,
+        market: Option<Market>,
+    ) -> ClientResult<Chapter> {
+        let params = build_map([("market", market.map(Into::into))]);
+
+        let url = format!("chapters/{}", id.id());
+        let result = self.endpoint_get(&url, &params).await?;
+        convert_result(&result)
+    }
+
+    /// Get Spotify catalog information for several chapters of audiobooks.
+    ///
+    /// Parameters:
+    /// - ids - a list of chapter IDs, URIs or URLs, which are requested in
+    ///   chunks of 50.
+    /// - market - an ISO 3166-1 alpha-2 country code or the string from_token.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-several-chapters)
+    async fn get_several_chapters<'a>(
+        &self,
+        ids: impl IntoIterator<Item = ChapterId<'a>> + Send + 'a,
+        market: Option<Market>,
+    ) -> ClientResult<Vec<Chapter>> {
+        let chunks = join_ids_chunked(ids, 50);
+        let chapters = request_chunks(
+            chunks,
+            self.get_config().chunk_concurrency,
+            |ids| async move {
+                let params = build_map([("ids", Some(&ids)), ("market", market.map(Into::into))]);
+                let result = self.endpoint_get("chapters", &params).await?;
+                convert_result::<ChaptersPayload>(&result).map(|x| x.chapters)
+            },
+        )
+        .await?;
+        Ok(chapters.into_iter().flatten().collect())
     }
 
     /// Get audio features for a track
@@ -713,22 +1223,41 @@ where
     /// Get Audio Features for Several Tracks
     ///
     /// Parameters:
-    /// - tracks a list of track URIs, URLs or IDs
+    /// - tracks a list of track URIs, URLs or IDs. They're requested in chunks
+    ///   of 100.
+    ///
+    /// The result has an element for each ID, in the same order, which is
+    /// `None` if the chunk it was requested in has no features.
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-several-audio-features)
     async fn tracks_features<'a>(
         &self,
         track_ids: impl IntoIterator<Item = TrackId<'a>> + Send + 'a,
-    ) -> ClientResult<Option<Vec<AudioFeatures>>> {
-        let url = format!("audio-features/?ids={}", join_ids(track_ids));
+    ) -> ClientResult<Vec<Option<AudioFeatures>>> {
+        let chunks = join_ids_chunked(track_ids, 100);
+        let features = request_chunks(
+            chunks,
+            self.get_config().chunk_concurrency,
+            |ids| async move {
+                let url = format!("audio-features/?ids={ids}");
+                let result = self.endpoint_get(&url, &Query::new()).await?;
+                let payload = if result.is_empty() {
+                    None
+                } else {
+                    convert_result::<Option<AudioFeaturesPayload>>(&result)?
+                };
+
+                // A chunk without features still takes a place for each of
+                // its IDs, so that the rest stay aligned with them
+                Ok(match payload {
+                    Some(payload) => payload.audio_features.into_iter().map(Some).collect(),
+                    None => ids.split(',').map(|_| None).collect::<Vec<_>>(),
+                })
+            },
+        )
+        .await?;
 
-        let result = self.endpoint_get(&url, &Query::new()).await?;
-        if result.is_empty() {
-            Ok(None)
-        } else {
-            convert_result::<Option<AudioFeaturesPayload>>(&result)
-                .map(|option_payload| option_payload.map(|x| x.audio_features))
-        }
+        Ok(features.into_iter().flatten().collect())
     }
 
     /// Get Audio Analysis for a Track
@@ -763,9 +1292,35 @@ where
         locale: Option<&'a str>,
         country: Option<Market>,
     ) -> Paginator<'_, ClientResult<Category>> {
-        paginate(
+        paginate_concurrent(
             move |limit, offset| self.categories_manual(locale, country, Some(limit), Some(offset)),
             self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::categories`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn categories_owned(
+        &self,
+        locale: Option<String>,
+        country: Option<Market>,
+    ) -> Paginator<'static, ClientResult<Category>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let (client, locale) = (Arc::clone(&client), locale.clone());
+                async move {
+                    client
+                        .categories_manual(locale.as_deref(), country, Some(limit), Some(offset))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
         )
     }
 
@@ -789,6 +1344,28 @@ where
         convert_result::<PageCategory>(&result).map(|x| x.categories)
     }
 
+    /// Get a single category used to tag items in Spotify.
+    ///
+    /// Parameters:
+    /// - category_id - The category id.
+    /// - country - An ISO 3166-1 alpha-2 country code or the string from_token.
+    /// - locale - The desired language, consisting of an ISO 639 language code
+    ///   and an ISO 3166-1 alpha-2 country code, joined by an underscore.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-a-category)
+    async fn category(
+        &self,
+        category_id: &str,
+        country: Option<Market>,
+        locale: Option<&str>,
+    ) -> ClientResult<Category> {
+        let params = build_map([("country", country.map(Into::into)), ("locale", locale)]);
+
+        let url = format!("browse/categories/{category_id}");
+        let result = self.endpoint_get(&url, &params).await?;
+        convert_result(&result)
+    }
+
     /// Get a list of playlists in a category in Spotify
     ///
     /// Parameters:
@@ -808,11 +1385,37 @@ where
         category_id: &'a str,
         country: Option<Market>,
     ) -> Paginator<'_, ClientResult<SimplifiedPlaylist>> {
-        paginate(
+        paginate_concurrent(
             move |limit, offset| {
                 self.category_playlists_manual(category_id, country, Some(limit), Some(offset))
             },
             self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::category_playlists`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn category_playlists_owned(
+        &self,
+        category_id: String,
+        country: Option<Market>,
+    ) -> Paginator<'static, ClientResult<SimplifiedPlaylist>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let (client, category_id) = (Arc::clone(&client), category_id.clone());
+                async move {
+                    client
+                        .category_playlists_manual(&category_id, country, Some(limit), Some(offset))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
         )
     }
 
@@ -896,9 +1499,34 @@ where
         &self,
         country: Option<Market>,
     ) -> Paginator<'_, ClientResult<SimplifiedAlbum>> {
-        paginate(
+        paginate_concurrent(
             move |limit, offset| self.new_releases_manual(country, Some(limit), Some(offset)),
             self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::new_releases`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn new_releases_owned(
+        &self,
+        country: Option<Market>,
+    ) -> Paginator<'static, ClientResult<SimplifiedAlbum>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let client = Arc::clone(&client);
+                async move {
+                    client
+                        .new_releases_manual(country, Some(limit), Some(offset))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
         )
     }
 
@@ -928,7 +1556,8 @@ where
     ///   as `min_acousticness` or `target_duration_ms`.
     /// - seed_artists - a list of artist IDs, URIs or URLs
     /// - seed_tracks - a list of artist IDs, URIs or URLs
-    /// - seed_genres - a list of genre names. Available genres for
+    /// - seed_genres - a list of genre names. The available ones are returned
+    ///   by [`Self::available_genre_seeds`].
     /// - market - An ISO 3166-1 alpha-2 country code or the string from_token. If provided, all
     ///   results will be playable in this country.
     /// - limit - The maximum number of items to return. Default: 20.
@@ -976,6 +1605,39 @@ where
         convert_result(&result)
     }
 
+    /// Get the genres that can be used as `seed_genres` in
+    /// [`Self::recommendations`].
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-recommendation-genres)
+    async fn available_genre_seeds(&self) -> ClientResult<Vec<String>> {
+        let result = self
+            .endpoint_get("recommendations/available-genre-seeds", &Query::new())
+            .await?;
+        convert_result::<GenreSeedsPayload>(&result).map(|x| x.genres)
+    }
+
+    /// Get the markets where Spotify is available.
+    ///
+    /// The codes that aren't a known [`Country`] are skipped.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-available-markets)
+    async fn available_markets(&self) -> ClientResult<Vec<Country>> {
+        let result = self.endpoint_get("markets", &Query::new()).await?;
+        let markets = convert_result::<MarketsPayload>(&result)?
+            .markets
+            .into_iter()
+            .filter_map(|code| match code.parse() {
+                Ok(country) => Some(country),
+                Err(_) => {
+                    log::warn!("Skipping unknown market {code}");
+                    None
+                }
+            })
+            .collect();
+
+        Ok(markets)
+    }
+
     /// Get full details of the items of a playlist owned by a user.
     ///
     /// Parameters:
@@ -995,7 +1657,7 @@ where
         fields: Option<&'a str>,
         market: Option<Market>,
     ) -> Paginator<'_, ClientResult<PlaylistItem>> {
-        paginate(
+        paginate_concurrent(
             move |limit, offset| {
                 self.playlist_items_manual(
                     playlist_id.as_ref(),
@@ -1006,6 +1668,40 @@ where
                 )
             },
             self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::playlist_items`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn playlist_items_owned(
+        &self,
+        playlist_id: PlaylistId<'static>,
+        fields: Option<String>,
+        market: Option<Market>,
+    ) -> Paginator<'static, ClientResult<PlaylistItem>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let (client, playlist_id, fields) =
+                    (Arc::clone(&client), playlist_id.clone(), fields.clone());
+                async move {
+                    client
+                        .playlist_items_manual(
+                            playlist_id.as_ref(),
+                            fields.as_deref(),
+                            market,
+                            Some(limit),
+                            Some(offset),
+                        )
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
         )
     }
 
@@ -1047,9 +1743,34 @@ where
         &'a self,
         user_id: &'a UserId<'_>,
     ) -> Paginator<'_, ClientResult<SimplifiedPlaylist>> {
-        paginate(
+        paginate_concurrent(
             move |limit, offset| self.user_playlists_manual(user_id, Some(limit), Some(offset)),
             self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::user_playlists`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn user_playlists_owned(
+        &self,
+        user_id: UserId<'static>,
+    ) -> Paginator<'static, ClientResult<SimplifiedPlaylist>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let (client, user_id) = (Arc::clone(&client), user_id.clone());
+                async move {
+                    client
+                        .user_playlists_manual(&user_id, Some(limit), Some(offset))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
         )
     }
 
@@ -1068,4 +1789,151 @@ where
         let result = self.endpoint_get(&url, &params).await?;
         convert_result(&result)
     }
+
+    /// Fetches the page after `page` by following its `next` link, or returns
+    /// `None` if it's already the last one. This is useful along with the
+    /// manually paginated endpoints, such as [`Self::artist_albums_manual`],
+    /// so that the offsets don't have to be computed by hand.
+    ///
+    /// The pages nested inside a wrapper object by the API, like the ones
+    /// returned by [`Self::search`], are also supported.
+    async fn next_page<T>(&self, page: &Page<T>) -> ClientResult<Option<Page<T>>>
+    where
+        T: DeserializeOwned + Send + Sync,
+    {
+        let url = match &page.next {
+            Some(url) => url,
+            None => return Ok(None),
+        };
+
+        let result = self.endpoint_get(url, &Query::new()).await?;
+        convert_page(&result, url).map(Some)
+    }
+
+    /// Fetches the page before `page` by following its `previous` link, or
+    /// returns `None` if it's already the first one.
+    ///
+    /// See [`Self::next_page`] for more information.
+    async fn previous_page<T>(&self, page: &Page<T>) -> ClientResult<Option<Page<T>>>
+    where
+        T: DeserializeOwned + Send + Sync,
+    {
+        let url = match &page.previous {
+            Some(url) => url,
+            None => return Ok(None),
+        };
+
+        let result = self.endpoint_get(url, &Query::new()).await?;
+        convert_page(&result, url).map(Some)
+    }
+
+    /// Fetches the cursor-based page after `page` by following its `next`
+    /// link, or returns `None` if it's already the last one.
+    ///
+    /// See [`Self::next_page`] for more information.
+    async fn next_cursor_page<T>(
+        &self,
+        page: &CursorBasedPage<T>,
+    ) -> ClientResult<Option<CursorBasedPage<T>>>
+    where
+        T: DeserializeOwned + Send + Sync,
+    {
+        let url = match &page.next {
+            Some(url) => url,
+            None => return Ok(None),
+        };
+
+        let result = self.endpoint_get(url, &Query::new()).await?;
+        convert_page(&result, url).map(Some)
+    }
+
+    /// Sends a request to any endpoint of the Spotify API, which is useful for
+    /// the ones that aren't wrapped by this client yet. It's authenticated like
+    /// the rest of endpoints, so the token is refreshed automatically when
+    /// enabled, and the request is retried according to
+    /// [`Config::retry_policy`](crate::Config::retry_policy).
+    ///
+    /// The response is deserialized into `T`. Empty responses, which are
+    /// common in `PUT` and `DELETE` endpoints, may be deserialized into `()`.
+    ///
+    /// See [`Self::api_request_paginated`] for paginated endpoints.
+    async fn api_request<T: DeserializeOwned>(&self, request: &ApiRequest) -> ClientResult<T> {
+        let result = self.endpoint_request(request).await?;
+        if result.trim().is_empty() {
+            convert_result("null")
+        } else {
+            convert_result(&result)
+        }
+    }
+
+    /// Automatically paginates the items of any endpoint that returns a
+    /// [`Page`], similarly to [`Self::api_request`]. The `limit` and `offset`
+    /// query parameters are set for each page, and the page may be nested
+    /// inside a wrapper object like `{"playlists": {...}}`.
+    fn api_request_paginated<'a, T>(&'a self, request: ApiRequest) -> Paginator<'a, ClientResult<T>>
+    where
+        T: 'a + DeserializeOwned + Unpin + Send,
+    {
+        paginate_concurrent(
+            move |limit, offset| {
+                let request = request
+                    .clone()
+                    .query("limit", limit.to_string())
+                    .query("offset", offset.to_string());
+                async move {
+                    let url =
+                        Url::parse_with_params(&self.endpoint_url(&request.path), &request.query)?;
+                    let result = self.endpoint_request(&request).await?;
+                    convert_page(&result, url.as_str())
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// Turns the manually paginated version of an endpoint into a paginator
+    /// that yields whole pages instead of their items, so that the progress of
+    /// a long pagination can be obtained with [`Paginator::progress`] while it
+    /// runs. The request receives the `limit` and `offset` of each page, and
+    /// pages are requested with [`Config::pagination_chunks`] items.
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "__async")]
+    /// # async fn run(spotify: rspotify::ClientCredsSpotify) {
+    /// use futures::stream::TryStreamExt;
+    /// use rspotify::{model::ArtistId, prelude::*};
+    ///
+    /// let artist = ArtistId::from_id("0OdUWJ0sBjDrqHygGUXeCF").unwrap();
+    /// let mut pages = spotify.pages(|limit, offset| {
+    ///     spotify.artist_albums_manual(&artist, None, None, Some(limit), Some(offset))
+    /// });
+    /// while let Some(page) = pages.try_next().await.unwrap() {
+    ///     println!("{} albums, {:?}", page.items.len(), pages.progress());
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "__async")]
+    fn pages<'a, T, Fut, Request>(&self, request: Request) -> Paginator<'a, ClientResult<Page<T>>>
+    where
+        T: 'a + Unpin + Send,
+        Fut: 'a + Future<Output = ClientResult<Page<T>>> + Send,
+        Request: 'a + Fn(u32, u32) -> Fut + Send + Clone,
+    {
+        paginate_pages(request, self.get_config().pagination_chunks)
+    }
+
+    /// Turns the manually paginated version of an endpoint into a paginator
+    /// that yields whole pages instead of their items, so that the progress of
+    /// a long pagination can be obtained with [`Paginator::progress`] while it
+    /// runs. The request receives the `limit` and `offset` of each page, and
+    /// pages are requested with [`Config::pagination_chunks`] items.
+    #[cfg(feature = "__sync")]
+    fn pages<'a, T, Request>(&self, request: Request) -> Paginator<'a, ClientResult<Page<T>>>
+    where
+        T: 'a + Send,
+        Request: 'a + Fn(u32, u32) -> ClientResult<Page<T>> + Send + Clone,
+    {
+        paginate_pages(request, self.get_config().pagination_chunks)
+    }
 }
@@ -1,21 +1,178 @@
+//! The clients and their endpoints, which are implemented in [`BaseClient`]
+//! and [`OAuthClient`].
+//!
+//! ## Limits of the bulk endpoints
+//!
+//! Some endpoints take a list of IDs, but the Spotify API only accepts a
+//! certain number of them per request. These endpoints split the IDs into
+//! chunks automatically, making a request for each of them and merging their
+//! results in order:
+//!
+//! | Endpoint                                              | IDs per request |
+//! |-------------------------------------------------------|-----------------|
+//! | [`BaseClient::tracks`]                                | 50              |
+//! | [`BaseClient::artists`]                               | 50              |
+//! | [`BaseClient::albums`]                                | 20              |
+//! | [`BaseClient::tracks_features`]                       | 100             |
+//! | [`BaseClient::get_several_episodes`]                  | 50              |
+//! | [`BaseClient::get_several_audiobooks`]                | 50              |
+//! | [`BaseClient::get_several_chapters`]                  | 50              |
+//! | [`OAuthClient::playlist_add_items`]                   | 100             |
+//! | [`OAuthClient::current_user_saved_tracks_add`]        | 50              |
+//! | [`OAuthClient::current_user_saved_tracks_delete`]     | 50              |
+//! | [`OAuthClient::current_user_saved_tracks_contains`]   | 50              |
+//! | [`OAuthClient::current_user_saved_episodes_add`]      | 50              |
+//! | [`OAuthClient::current_user_saved_episodes_delete`]   | 50              |
+//! | [`OAuthClient::current_user_saved_episodes_contains`] | 50              |
+//!
+//! In async mode, up to [`Config::chunk_concurrency`] chunks are requested at
+//! the same time. The exception is [`OAuthClient::playlist_add_items`], whose
+//! chunks are always added one after the other so that the items keep their
+//! order.
+//!
+//! Note that a request that fails after some chunks have succeeded leaves
+//! their changes in place.
+
 mod base;
 mod oauth;
 pub mod pagination;
+mod request;
 
 pub use base::BaseClient;
 pub use oauth::OAuthClient;
+pub use request::{ApiRequest, Method};
 
-use crate::ClientResult;
+use crate::{http::Headers, model::Page, ClientResult, Config, Token};
 
 use std::fmt::Write as _;
 
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "__async")]
+use futures::{
+    future::Future,
+    stream::{self, StreamExt, TryStreamExt},
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
+use url::Url;
+
+/// Parses the response of the token endpoint.
+///
+/// The token expires `expires_in` after the `Date` header of the response,
+/// so its expiration date is kept on Spotify's clock. The skew between both
+/// clocks at the time of the response is saved along with it, so that the
+/// local time can be converted into Spotify's when checking if it has expired.
+/// Without the header, the local clock is used directly.
+pub(in crate) fn parse_token(response: &str, headers: &Headers) -> ClientResult<Token> {
+    let mut tok = serde_json::from_str::<Token>(response)?;
+
+    let now = Utc::now();
+    let server_date = headers
+        .get("date")
+        .and_then(|date| DateTime::parse_from_rfc2822(date).ok())
+        .map(|date| date.with_timezone(&Utc));
+    let issued_at = server_date.unwrap_or(now);
+
+    tok.clock_skew = issued_at - now;
+    tok.expires_at = issued_at.checked_add_signed(tok.expires_in);
+    Ok(tok)
+}
+
+/// Checks if the token is expired with the margin in the configuration.
+pub(in crate) fn is_token_expired(token: &Token, config: &Config) -> bool {
+    token.is_expired_with_margin(config.token_expiry_margin)
+}
 
 /// Converts a JSON response from Spotify into its model.
 pub(in crate) fn convert_result<'a, T: Deserialize<'a>>(input: &'a str) -> ClientResult<T> {
     serde_json::from_str::<T>(input).map_err(Into::into)
 }
 
+/// Converts a JSON response with a page from Spotify into its model.
+///
+/// Some endpoints like `search` nest their pages inside a wrapper object, e.g.
+/// `{"tracks": {...}}`. In that case the page that was requested from `url`
+/// is taken, or the only one if there's a single page in the wrapper. The
+/// `href` of each page is compared to `url` with its query parameters in any
+/// order, as Spotify may return them in a different one.
+pub(in crate) fn convert_page<T: DeserializeOwned>(input: &str, url: &str) -> ClientResult<T> {
+    let mut value: Value = serde_json::from_str(input)?;
+    let wrapped = match &mut value {
+        Value::Object(map) if !map.contains_key("items") => {
+            let is_page = |page: &Value| page.get("items").is_some();
+            let mut pages = map.iter().filter(|(_, page)| is_page(page));
+            let requested = pages
+                .clone()
+                .find(|(_, page)| matches!(page.get("href").and_then(Value::as_str), Some(href) if same_url(href, url)));
+            let key = match (requested, pages.next(), pages.next()) {
+                (Some((key, _)), _, _) | (None, Some((key, _)), None) => Some(key.clone()),
+                _ => None,
+            };
+            key.and_then(|key| map.remove(&key))
+        }
+        _ => None,
+    };
+
+    serde_json::from_value(wrapped.unwrap_or(value)).map_err(Into::into)
+}
+
+/// Spotify only serves the results of a search up to this offset.
+pub(in crate) const SEARCH_WINDOW: u32 = 1000;
+
+/// Ends a page of search results at [`SEARCH_WINDOW`], so that the results
+/// beyond it aren't requested.
+pub(in crate) fn within_search_window<T>(mut page: Page<T>) -> Page<T> {
+    page.total = page.total.min(SEARCH_WINDOW);
+    if page.offset + page.items.len() as u32 >= SEARCH_WINDOW {
+        page.next = None;
+    }
+    page
+}
+
+/// Whether both URLs are the same, regardless of the order of their query
+/// parameters.
+fn same_url(a: &str, b: &str) -> bool {
+    let parts = |url: &str| {
+        let mut url = Url::parse(url).ok()?;
+        let mut query = url.query_pairs().into_owned().collect::<Vec<_>>();
+        query.sort_unstable();
+        url.set_query(None);
+        Some((url, query))
+    };
+    a == b || matches!((parts(a), parts(b)), (Some(a), Some(b)) if a == b)
+}
+
+/// Makes a request for each chunk of IDs returned by
+/// [`join_ids_chunked`](crate::join_ids_chunked), up to `concurrency` at the
+/// same time, and returns their results in the same order.
+#[cfg(feature = "__async")]
+pub(in crate) async fn request_chunks<T, Fut>(
+    chunks: Vec<String>,
+    concurrency: usize,
+    request: impl Fn(String) -> Fut,
+) -> ClientResult<Vec<T>>
+where
+    Fut: Future<Output = ClientResult<T>>,
+{
+    stream::iter(chunks)
+        .map(request)
+        .buffered(concurrency)
+        .try_collect()
+        .await
+}
+
+/// Makes a request for each chunk of IDs returned by
+/// [`join_ids_chunked`](crate::join_ids_chunked) and returns their results in
+/// the same order. The chunks are always requested one at a time.
+#[cfg(feature = "__sync")]
+pub(in crate) fn request_chunks<T>(
+    chunks: Vec<String>,
+    _concurrency: usize,
+    request: impl Fn(String) -> ClientResult<T>,
+) -> ClientResult<Vec<T>> {
+    chunks.into_iter().map(request).collect()
+}
+
 /// Append device ID to an API path.
 pub(in crate) fn append_device_id(path: &str, device_id: Option<&str>) -> String {
     let mut new_path = path.to_string();
@@ -33,7 +190,7 @@ pub(in crate) fn append_device_id(path: &str, device_id: Option<&str>) -> String
 mod test {
     use super::*;
     use crate::{model::Token, scopes, ClientCredsSpotify};
-    use chrono::{prelude::*, Duration};
+    use chrono::Duration;
 
     #[test]
     fn test_append_device_id_without_question_mark() {
@@ -54,6 +211,72 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_convert_page() {
+        use crate::model::Page;
+
+        let page = |href: &str, item: u32| {
+            format!(
+                r#"{{"href": "{}", "items": [{}], "limit": 1, "next": null, "offset": 0, "previous": null, "total": 2}}"#,
+                href, item
+            )
+        };
+        let url = "https://api.spotify.com/v1/search?q=a&type=track,artist&offset=1";
+        let items = |input: &str| convert_page::<Page<u32>>(input, url).map(|page| page.items);
+
+        // Not wrapped
+        assert_eq!(items(&page(url, 1)).unwrap(), vec![1]);
+
+        // Wrapped with a single page
+        let wrapped = format!(r#"{{"tracks": {}}}"#, page("other", 1));
+        assert_eq!(items(&wrapped).unwrap(), vec![1]);
+
+        // Wrapped with multiple pages, only one of them was requested
+        let wrapped = format!(
+            r#"{{"artists": {}, "tracks": {}}}"#,
+            page("other", 1),
+            page(url, 2)
+        );
+        assert_eq!(items(&wrapped).unwrap(), vec![2]);
+
+        // Wrapped with multiple pages, with the query in a different order
+        let reordered = "https://api.spotify.com/v1/search?offset=1&type=track,artist&q=a";
+        let wrapped = format!(
+            r#"{{"artists": {}, "tracks": {}}}"#,
+            page("other", 1),
+            page(reordered, 2)
+        );
+        assert_eq!(items(&wrapped).unwrap(), vec![2]);
+
+        // Wrapped with multiple pages, none of them requested
+        let wrapped = format!(
+            r#"{{"artists": {}, "tracks": {}}}"#,
+            page("other", 1),
+            page("other", 2)
+        );
+        assert!(items(&wrapped).is_err());
+    }
+
+    #[test]
+    fn test_within_search_window() {
+        let page = |offset: u32, len: usize, total: u32| Page {
+            href: String::new(),
+            items: vec![0; len],
+            limit: 50,
+            next: Some("next".to_owned()),
+            offset,
+            previous: None,
+            total,
+        };
+
+        let inside = within_search_window(page(900, 50, 5000));
+        assert_eq!((inside.total, inside.next.is_some()), (1000, true));
+        let last = within_search_window(page(950, 50, 5000));
+        assert_eq!((last.total, last.next.is_some()), (1000, false));
+        let small = within_search_window(page(0, 10, 20));
+        assert_eq!((small.total, small.next.is_some()), (20, true));
+    }
+
     #[test]
     fn test_endpoint_url() {
         let spotify = ClientCredsSpotify::default();
@@ -77,6 +300,7 @@ mod test {
             access_token: "test-access_token".to_string(),
             expires_in: Duration::seconds(1),
             expires_at: Some(Utc::now()),
+            clock_skew: Duration::zero(),
             scopes: scopes!("playlist-read-private"),
             refresh_token: Some("...".to_string()),
         };
@@ -88,4 +312,39 @@ mod test {
             Some(&"Bearer test-access_token".to_owned())
         );
     }
+
+    #[test]
+    fn test_parse_token_clock_skew() {
+        let response = r#"{"access_token": "token", "expires_in": 3600}"#;
+        let about =
+            |actual: Duration, expected: Duration| (actual - expected).num_seconds().abs() <= 1;
+
+        // Spotify's clock is an hour behind the local one, so the token
+        // expires when the local clock says it's an hour later.
+        let server_date = Utc::now() - Duration::hours(1);
+        let mut headers = Headers::new();
+        headers.insert("date".to_owned(), server_date.to_rfc2822());
+        let tok = parse_token(response, &headers).unwrap();
+        assert!(about(
+            tok.expires_at.unwrap() - Utc::now(),
+            Duration::zero()
+        ));
+        assert!(about(tok.clock_skew, Duration::hours(-1)));
+        assert!(!tok.is_expired());
+
+        let mut config = Config::default();
+        assert!(!is_token_expired(&tok, &config));
+        config.token_expiry_margin = Duration::minutes(59);
+        assert!(!is_token_expired(&tok, &config));
+        config.token_expiry_margin = Duration::minutes(61);
+        assert!(is_token_expired(&tok, &config));
+
+        // Without the header the local clock is used
+        let tok = parse_token(response, &Headers::new()).unwrap();
+        assert!(about(
+            tok.expires_at.unwrap() - Utc::now(),
+            Duration::hours(1)
+        ));
+        assert!(about(tok.clock_skew, Duration::zero()));
+    }
 }
@@ -1,18 +1,19 @@
 use crate::{
     clients::{
-        append_device_id, convert_result,
-        pagination::{paginate, Paginator},
-        BaseClient,
+        append_device_id, convert_result, is_token_expired,
+        pagination::{paginate_concurrent, paginate_with_cursor, CursorDirection, Paginator},
+        request_chunks, BaseClient,
     },
     http::Query,
-    join_ids,
+    join_ids, join_ids_chunked,
     model::*,
     util::{build_map, JsonBuilder},
-    ClientResult, OAuth, Token,
+    ClientError, ClientResult, OAuth, Token,
 };
 
-use std::{collections::HashMap, time};
+use std::{collections::HashMap, sync::Arc, time};
 
+use chrono::{TimeZone, Utc};
 use maybe_async::maybe_async;
 use rspotify_model::idtypes::{PlayContextId, PlayableId};
 use serde_json::{json, Map};
@@ -63,9 +64,9 @@ pub trait OAuthClient: BaseClient {
         }
 
         log::info!("Reading auth token cache");
-        let token = Token::from_cache(&self.get_config().cache_path)?;
+        let token = self.get_config().read_cache()?;
         if !self.get_oauth().scopes.is_subset(&token.scopes)
-            || (!allow_expired && token.is_expired())
+            || (!allow_expired && is_token_expired(&token, self.get_config()))
         {
             // Invalid token, since it doesn't have at least the currently
             // required scopes or it's expired.
@@ -105,8 +106,6 @@ pub trait OAuthClient: BaseClient {
     /// Note: this method requires the `cli` feature.
     #[cfg(feature = "cli")]
     fn get_code_from_user(&self, url: &str) -> ClientResult<String> {
-        use crate::ClientError;
-
         log::info!("Opening brower with auth URL");
         match webbrowser::open(url) {
             Ok(_) => println!("Opened {} in your browser.", url),
@@ -147,7 +146,7 @@ pub trait OAuthClient: BaseClient {
     async fn prompt_for_token(&self, url: &str) -> ClientResult<()> {
         match self.read_token_cache(true).await {
             Ok(Some(new_token)) => {
-                let expired = new_token.is_expired();
+                let expired = is_token_expired(&new_token, self.get_config());
 
                 // Load token into client regardless of whether it's expired o
                 // not, since it will be refreshed later anyway.
@@ -179,6 +178,30 @@ pub trait OAuthClient: BaseClient {
         self.write_token_cache().await
     }
 
+    /// Signs the user out, so that the client can start a fresh authorization
+    /// afterwards.
+    ///
+    /// This clears the token in memory and, if [`Config::token_cached`] is
+    /// enabled, removes it from the cache as well. The clients included in
+    /// this crate also regenerate the OAuth state, and in the PKCE flow, the
+    /// code verifier is reset.
+    ///
+    /// Note that Spotify doesn't provide an endpoint to revoke tokens, so the
+    /// access token will still be valid until it expires. The user may revoke
+    /// the access to your application in their account settings.
+    ///
+    /// [`Config::token_cached`]: crate::Config::token_cached
+    async fn logout(&mut self) -> ClientResult<()> {
+        log::info!("Logging out");
+
+        *self.get_token().lock().await.unwrap() = None;
+        if self.get_config().token_cached {
+            self.get_config().remove_cache()?;
+        }
+
+        Ok(())
+    }
+
     /// Get current user playlists without required getting his profile.
     ///
     /// Parameters:
@@ -190,9 +213,31 @@ pub trait OAuthClient: BaseClient {
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-a-list-of-current-users-playlists)
     fn current_user_playlists(&self) -> Paginator<'_, ClientResult<SimplifiedPlaylist>> {
-        paginate(
+        paginate_concurrent(
             move |limit, offset| self.current_user_playlists_manual(Some(limit), Some(offset)),
             self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::current_user_playlists`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn current_user_playlists_owned(&self) -> Paginator<'static, ClientResult<SimplifiedPlaylist>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let client = Arc::clone(&client);
+                async move {
+                    client
+                        .current_user_playlists_manual(Some(limit), Some(offset))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
         )
     }
 
@@ -276,6 +321,38 @@ pub trait OAuthClient: BaseClient {
         self.endpoint_put(&url, &params).await
     }
 
+    /// Replaces the cover image of a playlist. It requires the
+    /// `ugc-image-upload` and `playlist-modify-public` or
+    /// `playlist-modify-private` scopes.
+    ///
+    /// The image is encoded in base64 before uploading it, and the resulting
+    /// payload can be at most 256 KB. Otherwise, [`ClientError::ImageTooLarge`]
+    /// is returned without making the request.
+    ///
+    /// Parameters:
+    /// - playlist_id - the id of the playlist
+    /// - image - the contents of a JPEG image
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/upload-custom-playlist-cover)
+    async fn playlist_upload_cover_image(
+        &self,
+        playlist_id: PlaylistId<'_>,
+        image: &[u8],
+    ) -> ClientResult<()> {
+        const MAX_SIZE: usize = 256 * 1024;
+
+        let body = base64::encode(image);
+        if body.len() > MAX_SIZE {
+            return Err(ClientError::ImageTooLarge(body.len()));
+        }
+
+        let url = format!("playlists/{}/images", playlist_id.id());
+        self.endpoint_put_raw(&url, body.as_bytes(), "image/jpeg")
+            .await?;
+
+        Ok(())
+    }
+
     /// Unfollows (deletes) a playlist for a user.
     ///
     /// Parameters:
@@ -293,7 +370,8 @@ pub trait OAuthClient: BaseClient {
     ///
     /// Parameters:
     /// - playlist_id - the id of the playlist
-    /// - track_ids - a list of track URIs, URLs or IDs
+    /// - track_ids - a list of track URIs, URLs or IDs. They're added in
+    ///   chunks of 100, one after the other.
     /// - position - the position to add the tracks
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/add-tracks-to-playlist)
@@ -303,15 +381,32 @@ pub trait OAuthClient: BaseClient {
         items: impl IntoIterator<Item = PlayableId<'a>> + Send + 'a,
         position: Option<i32>,
     ) -> ClientResult<PlaylistResult> {
-        let uris = items.into_iter().map(|id| id.uri()).collect::<Vec<_>>();
-        let params = JsonBuilder::new()
-            .required("uris", uris)
-            .optional("position", position)
-            .build();
+        const LIMIT: usize = 100;
 
+        let uris = items.into_iter().map(|id| id.uri()).collect::<Vec<_>>();
         let url = format!("playlists/{}/tracks", playlist_id.id());
-        let result = self.endpoint_post(&url, &params).await?;
-        convert_result(&result)
+
+        // At least one request is made, even if there are no items, so that
+        // the snapshot of the playlist is returned
+        let mut chunks = uris.chunks(LIMIT).collect::<Vec<_>>();
+        if chunks.is_empty() {
+            chunks.push(&[]);
+        }
+
+        let mut result = None;
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            // Each chunk is inserted after the previous one
+            let position = position.map(|position| position + (i * LIMIT) as i32);
+            let params = JsonBuilder::new()
+                .required("uris", chunk)
+                .optional("position", position)
+                .build();
+
+            let response = self.endpoint_post(&url, &params).await?;
+            result = Some(convert_result(&response)?);
+        }
+
+        Ok(result.expect("at least one chunk is always added"))
     }
 
     /// Replace all items in a playlist
@@ -523,11 +618,36 @@ pub trait OAuthClient: BaseClient {
         &self,
         market: Option<Market>,
     ) -> Paginator<'_, ClientResult<SavedAlbum>> {
-        paginate(
+        paginate_concurrent(
             move |limit, offset| {
                 self.current_user_saved_albums_manual(market, Some(limit), Some(offset))
             },
             self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::current_user_saved_albums`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn current_user_saved_albums_owned(
+        &self,
+        market: Option<Market>,
+    ) -> Paginator<'static, ClientResult<SavedAlbum>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let client = Arc::clone(&client);
+                async move {
+                    client
+                        .current_user_saved_albums_manual(market, Some(limit), Some(offset))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
         )
     }
 
@@ -566,11 +686,36 @@ pub trait OAuthClient: BaseClient {
         &self,
         market: Option<Market>,
     ) -> Paginator<'_, ClientResult<SavedTrack>> {
-        paginate(
+        paginate_concurrent(
             move |limit, offset| {
                 self.current_user_saved_tracks_manual(market, Some(limit), Some(offset))
             },
             self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::current_user_saved_tracks`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn current_user_saved_tracks_owned(
+        &self,
+        market: Option<Market>,
+    ) -> Paginator<'static, ClientResult<SavedTrack>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let client = Arc::clone(&client);
+                async move {
+                    client
+                        .current_user_saved_tracks_manual(market, Some(limit), Some(offset))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
         )
     }
 
@@ -595,12 +740,49 @@ pub trait OAuthClient: BaseClient {
 
     /// Gets a list of the artists followed by the current authorized user.
     ///
-    /// Parameters:
-    /// - after - the last artist ID retrieved from the previous request
-    /// - limit - the number of tracks to return
+    /// See [`Self::current_user_followed_artists_manual`] for a manually
+    /// paginated version of this.
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-followed)
-    async fn current_user_followed_artists(
+    fn current_user_followed_artists(&self) -> Paginator<'_, ClientResult<FullArtist>> {
+        paginate_with_cursor(
+            move |limit, after| async move {
+                self.current_user_followed_artists_manual(after.as_deref(), Some(limit))
+                    .await
+            },
+            self.get_config().pagination_chunks,
+            CursorDirection::After,
+        )
+    }
+
+    /// The owned version of [`Self::current_user_followed_artists`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn current_user_followed_artists_owned(&self) -> Paginator<'static, ClientResult<FullArtist>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_with_cursor(
+            move |limit, after| {
+                let client = Arc::clone(&client);
+                async move {
+                    client
+                        .current_user_followed_artists_manual(after.as_deref(), Some(limit))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            CursorDirection::After,
+        )
+    }
+
+    /// The manually paginated version of
+    /// [`Self::current_user_followed_artists`].
+    ///
+    /// Parameters:
+    /// - after - the last artist ID retrieved from the previous request
+    /// - limit - the number of artists to return
+    async fn current_user_followed_artists_manual(
         &self,
         after: Option<&str>,
         limit: Option<u32>,
@@ -619,15 +801,24 @@ pub trait OAuthClient: BaseClient {
     /// Remove one or more tracks from the current user's "Your Music" library.
     ///
     /// Parameters:
-    /// - track_ids - a list of track URIs, URLs or IDs
+    /// - track_ids - a list of track URIs, URLs or IDs. They're requested in
+    ///   chunks of 50.
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/remove-tracks-user)
     async fn current_user_saved_tracks_delete<'a>(
         &self,
         track_ids: impl IntoIterator<Item = TrackId<'a>> + Send + 'a,
     ) -> ClientResult<()> {
-        let url = format!("me/tracks/?ids={}", join_ids(track_ids));
-        self.endpoint_delete(&url, &json!({})).await?;
+        let chunks = join_ids_chunked(track_ids, 50);
+        request_chunks(
+            chunks,
+            self.get_config().chunk_concurrency,
+            |ids| async move {
+                let url = format!("me/tracks/?ids={ids}");
+                self.endpoint_delete(&url, &json!({})).await
+            },
+        )
+        .await?;
 
         Ok(())
     }
@@ -636,34 +827,196 @@ pub trait OAuthClient: BaseClient {
     /// user’s "Your Music" library.
     ///
     /// Parameters:
-    /// - track_ids - a list of track URIs, URLs or IDs
+    /// - track_ids - a list of track URIs, URLs or IDs. They're requested in
+    ///   chunks of 50.
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/check-users-saved-tracks)
     async fn current_user_saved_tracks_contains<'a>(
         &self,
         track_ids: impl IntoIterator<Item = TrackId<'a>> + Send + 'a,
     ) -> ClientResult<Vec<bool>> {
-        let url = format!("me/tracks/contains/?ids={}", join_ids(track_ids));
-        let result = self.endpoint_get(&url, &Query::new()).await?;
-        convert_result(&result)
+        let chunks = join_ids_chunked(track_ids, 50);
+        let contains = request_chunks(
+            chunks,
+            self.get_config().chunk_concurrency,
+            |ids| async move {
+                let url = format!("me/tracks/contains/?ids={ids}");
+                let result = self.endpoint_get(&url, &Query::new()).await?;
+                convert_result::<Vec<bool>>(&result)
+            },
+        )
+        .await?;
+        Ok(contains.into_iter().flatten().collect())
     }
 
     /// Save one or more tracks to the current user's "Your Music" library.
     ///
     /// Parameters:
-    /// - track_ids - a list of track URIs, URLs or IDs
+    /// - track_ids - a list of track URIs, URLs or IDs. They're requested in
+    ///   chunks of 50.
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/save-tracks-user)
     async fn current_user_saved_tracks_add<'a>(
         &self,
         track_ids: impl IntoIterator<Item = TrackId<'a>> + Send + 'a,
     ) -> ClientResult<()> {
-        let url = format!("me/tracks/?ids={}", join_ids(track_ids));
-        self.endpoint_put(&url, &json!({})).await?;
+        let chunks = join_ids_chunked(track_ids, 50);
+        request_chunks(
+            chunks,
+            self.get_config().chunk_concurrency,
+            |ids| async move {
+                let url = format!("me/tracks/?ids={ids}");
+                self.endpoint_put(&url, &json!({})).await
+            },
+        )
+        .await?;
 
         Ok(())
     }
 
+    /// Get a list of the episodes saved in the current Spotify user's library.
+    ///
+    /// Parameters:
+    /// - limit - the number of episodes to return
+    /// - offset - the index of the first episode to return
+    /// - market - an ISO 3166-1 alpha-2 country code or the string from_token.
+    ///
+    /// See [`Self::current_user_saved_episodes_manual`] for a manually
+    /// paginated version of this.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-users-saved-episodes)
+    fn current_user_saved_episodes(
+        &self,
+        market: Option<Market>,
+    ) -> Paginator<'_, ClientResult<SavedEpisode>> {
+        paginate_concurrent(
+            move |limit, offset| {
+                self.current_user_saved_episodes_manual(market, Some(limit), Some(offset))
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::current_user_saved_episodes`], which
+    /// borrows nothing so that it can be moved to other tasks or threads.
+    fn current_user_saved_episodes_owned(
+        &self,
+        market: Option<Market>,
+    ) -> Paginator<'static, ClientResult<SavedEpisode>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let client = Arc::clone(&client);
+                async move {
+                    client
+                        .current_user_saved_episodes_manual(market, Some(limit), Some(offset))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The manually paginated version of [`Self::current_user_saved_episodes`].
+    async fn current_user_saved_episodes_manual(
+        &self,
+        market: Option<Market>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> ClientResult<Page<SavedEpisode>> {
+        let limit = limit.map(|s| s.to_string());
+        let offset = offset.map(|s| s.to_string());
+        let params = build_map([
+            ("market", market.map(Into::into)),
+            ("limit", limit.as_deref()),
+            ("offset", offset.as_deref()),
+        ]);
+
+        let result = self.endpoint_get("me/episodes", &params).await?;
+        convert_result(&result)
+    }
+
+    /// Save one or more episodes to the current user's library.
+    ///
+    /// Parameters:
+    /// - episode_ids - a list of episode URIs, URLs or IDs. They're requested
+    ///   in chunks of 50.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/save-episodes-user)
+    async fn current_user_saved_episodes_add<'a>(
+        &self,
+        episode_ids: impl IntoIterator<Item = EpisodeId<'a>> + Send + 'a,
+    ) -> ClientResult<()> {
+        let chunks = join_ids_chunked(episode_ids, 50);
+        request_chunks(
+            chunks,
+            self.get_config().chunk_concurrency,
+            |ids| async move {
+                let url = format!("me/episodes/?ids={ids}");
+                self.endpoint_put(&url, &json!({})).await
+            },
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remove one or more episodes from the current user's library.
+    ///
+    /// Parameters:
+    /// - episode_ids - a list of episode URIs, URLs or IDs. They're requested
+    ///   in chunks of 50.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/remove-episodes-user)
+    async fn current_user_saved_episodes_delete<'a>(
+        &self,
+        episode_ids: impl IntoIterator<Item = EpisodeId<'a>> + Send + 'a,
+    ) -> ClientResult<()> {
+        let chunks = join_ids_chunked(episode_ids, 50);
+        request_chunks(
+            chunks,
+            self.get_config().chunk_concurrency,
+            |ids| async move {
+                let url = format!("me/episodes/?ids={ids}");
+                self.endpoint_delete(&url, &json!({})).await
+            },
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Check if one or more episodes are already saved in the current user's
+    /// library.
+    ///
+    /// Parameters:
+    /// - episode_ids - a list of episode URIs, URLs or IDs. They're requested
+    ///   in chunks of 50.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/check-users-saved-episodes)
+    async fn current_user_saved_episodes_contains<'a>(
+        &self,
+        episode_ids: impl IntoIterator<Item = EpisodeId<'a>> + Send + 'a,
+    ) -> ClientResult<Vec<bool>> {
+        let chunks = join_ids_chunked(episode_ids, 50);
+        let contains = request_chunks(
+            chunks,
+            self.get_config().chunk_concurrency,
+            |ids| async move {
+                let url = format!("me/episodes/contains/?ids={ids}");
+                let result = self.endpoint_get(&url, &Query::new()).await?;
+                convert_result::<Vec<bool>>(&result)
+            },
+        )
+        .await?;
+        Ok(contains.into_iter().flatten().collect())
+    }
+
     /// Get the current user's top artists.
     ///
     /// Parameters:
@@ -679,11 +1032,36 @@ pub trait OAuthClient: BaseClient {
         &self,
         time_range: Option<TimeRange>,
     ) -> Paginator<'_, ClientResult<FullArtist>> {
-        paginate(
+        paginate_concurrent(
             move |limit, offset| {
                 self.current_user_top_artists_manual(time_range, Some(limit), Some(offset))
             },
             self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::current_user_top_artists`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn current_user_top_artists_owned(
+        &self,
+        time_range: Option<TimeRange>,
+    ) -> Paginator<'static, ClientResult<FullArtist>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let client = Arc::clone(&client);
+                async move {
+                    client
+                        .current_user_top_artists_manual(time_range, Some(limit), Some(offset))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
         )
     }
 
@@ -721,11 +1099,36 @@ pub trait OAuthClient: BaseClient {
         &self,
         time_range: Option<TimeRange>,
     ) -> Paginator<'_, ClientResult<FullTrack>> {
-        paginate(
+        paginate_concurrent(
             move |limit, offset| {
                 self.current_user_top_tracks_manual(time_range, Some(limit), Some(offset))
             },
             self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::current_user_top_tracks`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn current_user_top_tracks_owned(
+        &self,
+        time_range: Option<TimeRange>,
+    ) -> Paginator<'static, ClientResult<FullTrack>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let client = Arc::clone(&client);
+                async move {
+                    client
+                        .current_user_top_tracks_manual(time_range, Some(limit), Some(offset))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
         )
     }
 
@@ -751,12 +1154,64 @@ pub trait OAuthClient: BaseClient {
     /// Get the current user's recently played tracks.
     ///
     /// Parameters:
+    /// - time_limit - returns all items after or before (but not including)
+    ///   this date. The pagination moves forward in time after
+    ///   [`TimeLimits::After`], and backwards otherwise.
+    ///
+    /// See [`Self::current_user_recently_played_manual`] for a manually
+    /// paginated version of this.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-recently-played)
+    fn current_user_recently_played(
+        &self,
+        time_limit: Option<TimeLimits>,
+    ) -> Paginator<'_, ClientResult<PlayHistory>> {
+        let direction = recently_played_direction(time_limit);
+        paginate_with_cursor(
+            move |limit, cursor| async move {
+                let time_limit = recently_played_time_limit(cursor, direction, time_limit)?;
+                self.current_user_recently_played_manual(Some(limit), time_limit)
+                    .await
+            },
+            self.get_config().pagination_chunks,
+            direction,
+        )
+    }
+
+    /// The owned version of [`Self::current_user_recently_played`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn current_user_recently_played_owned(
+        &self,
+        time_limit: Option<TimeLimits>,
+    ) -> Paginator<'static, ClientResult<PlayHistory>>
+    where
+        Self: 'static,
+    {
+        let direction = recently_played_direction(time_limit);
+        let client = Arc::new(self.clone());
+        paginate_with_cursor(
+            move |limit, cursor| {
+                let client = Arc::clone(&client);
+                async move {
+                    let time_limit = recently_played_time_limit(cursor, direction, time_limit)?;
+                    client
+                        .current_user_recently_played_manual(Some(limit), time_limit)
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            direction,
+        )
+    }
+
+    /// The manually paginated version of
+    /// [`Self::current_user_recently_played`].
+    ///
+    /// Parameters:
     /// - limit - the number of entities to return
     /// - time_limit - a Unix timestamp in milliseconds. Returns all items after
     /// or before (but not including) this cursor position.
-    ///
-    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-recently-played)
-    async fn current_user_recently_played(
+    async fn current_user_recently_played_manual(
         &self,
         limit: Option<u32>,
         time_limit: Option<TimeLimits>,
@@ -1235,6 +1690,37 @@ pub trait OAuthClient: BaseClient {
         Ok(())
     }
 
+    /// Get the currently playing item and the items in the user's playback
+    /// queue.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-queue)
+    async fn current_user_queue(&self) -> ClientResult<CurrentUserQueue> {
+        let result = self.endpoint_get("me/player/queue", &Query::new()).await?;
+        convert_result(&result)
+    }
+
+    /// Add an item to the end of the user's playback queue, unless it's
+    /// already in it. Returns whether the item was added.
+    ///
+    /// Parameters:
+    /// - uri - The uri of the item to add, Track or Episode
+    /// - device id - The id of the device targeting
+    ///
+    /// See [`Self::add_item_to_queue`] and [`Self::current_user_queue`] for
+    /// more information.
+    async fn add_item_to_queue_if_missing(
+        &self,
+        item: PlayableId<'_>,
+        device_id: Option<&str>,
+    ) -> ClientResult<bool> {
+        if self.current_user_queue().await?.contains(&item) {
+            return Ok(false);
+        }
+
+        self.add_item_to_queue(item, device_id).await?;
+        Ok(true)
+    }
+
     /// Add a show or a list of shows to a user’s library.
     ///
     /// Parameters:
@@ -1266,9 +1752,31 @@ pub trait OAuthClient: BaseClient {
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-users-saved-shows)
     fn get_saved_show(&self) -> Paginator<'_, ClientResult<Show>> {
-        paginate(
+        paginate_concurrent(
             move |limit, offset| self.get_saved_show_manual(Some(limit), Some(offset)),
             self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::get_saved_show`], which borrows nothing so
+    /// that it can be moved to other tasks or threads.
+    fn get_saved_show_owned(&self) -> Paginator<'static, ClientResult<Show>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let client = Arc::clone(&client);
+                async move {
+                    client
+                        .get_saved_show_manual(Some(limit), Some(offset))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
         )
     }
 
@@ -1323,4 +1831,144 @@ pub trait OAuthClient: BaseClient {
 
         Ok(())
     }
+
+    /// Get a list of the audiobooks saved in the current Spotify user's
+    /// library.
+    ///
+    /// Parameters:
+    /// - limit - the number of audiobooks to return
+    /// - offset - the index of the first audiobook to return
+    ///
+    /// See [`Self::current_user_saved_audiobooks_manual`] for a manually
+    /// paginated version of this.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-users-saved-audiobooks)
+    fn current_user_saved_audiobooks(&self) -> Paginator<'_, ClientResult<SimplifiedAudiobook>> {
+        paginate_concurrent(
+            move |limit, offset| {
+                self.current_user_saved_audiobooks_manual(Some(limit), Some(offset))
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The owned version of [`Self::current_user_saved_audiobooks`], which
+    /// borrows nothing so that it can be moved to other tasks or threads.
+    fn current_user_saved_audiobooks_owned(
+        &self,
+    ) -> Paginator<'static, ClientResult<SimplifiedAudiobook>>
+    where
+        Self: 'static,
+    {
+        let client = Arc::new(self.clone());
+        paginate_concurrent(
+            move |limit, offset| {
+                let client = Arc::clone(&client);
+                async move {
+                    client
+                        .current_user_saved_audiobooks_manual(Some(limit), Some(offset))
+                        .await
+                }
+            },
+            self.get_config().pagination_chunks,
+            self.get_config().pagination_concurrency,
+        )
+    }
+
+    /// The manually paginated version of
+    /// [`Self::current_user_saved_audiobooks`].
+    async fn current_user_saved_audiobooks_manual(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> ClientResult<Page<SimplifiedAudiobook>> {
+        let limit = limit.map(|x| x.to_string());
+        let offset = offset.map(|x| x.to_string());
+        let params = build_map([("limit", limit.as_deref()), ("offset", offset.as_deref())]);
+
+        let result = self.endpoint_get("me/audiobooks", &params).await?;
+        convert_result(&result)
+    }
+
+    /// Save one or more audiobooks to the current user's library.
+    ///
+    /// Parameters:
+    /// - audiobook_ids - a list of audiobook URIs, URLs or IDs
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/save-audiobooks-user)
+    async fn current_user_saved_audiobooks_add<'a>(
+        &self,
+        audiobook_ids: impl IntoIterator<Item = AudiobookId<'a>> + Send + 'a,
+    ) -> ClientResult<()> {
+        let url = format!("me/audiobooks?ids={}", join_ids(audiobook_ids));
+        self.endpoint_put(&url, &json!({})).await?;
+
+        Ok(())
+    }
+
+    /// Remove one or more audiobooks from the current user's library.
+    ///
+    /// Parameters:
+    /// - audiobook_ids - a list of audiobook URIs, URLs or IDs
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/remove-audiobooks-user)
+    async fn current_user_saved_audiobooks_delete<'a>(
+        &self,
+        audiobook_ids: impl IntoIterator<Item = AudiobookId<'a>> + Send + 'a,
+    ) -> ClientResult<()> {
+        let url = format!("me/audiobooks?ids={}", join_ids(audiobook_ids));
+        self.endpoint_delete(&url, &json!({})).await?;
+
+        Ok(())
+    }
+
+    /// Check if one or more audiobooks are already saved in the current user's
+    /// library.
+    ///
+    /// Parameters:
+    /// - audiobook_ids - a list of audiobook URIs, URLs or IDs
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/check-users-saved-audiobooks)
+    async fn current_user_saved_audiobooks_contains<'a>(
+        &self,
+        audiobook_ids: impl IntoIterator<Item = AudiobookId<'a>> + Send + 'a,
+    ) -> ClientResult<Vec<bool>> {
+        let url = format!("me/audiobooks/contains?ids={}", join_ids(audiobook_ids));
+        let result = self.endpoint_get(&url, &Query::new()).await?;
+        convert_result(&result)
+    }
+}
+
+/// The recently played tracks are paginated forward in time after
+/// [`TimeLimits::After`], and backwards otherwise.
+fn recently_played_direction(time_limit: Option<TimeLimits>) -> CursorDirection {
+    match time_limit {
+        Some(TimeLimits::After(_)) => CursorDirection::After,
+        _ => CursorDirection::Before,
+    }
+}
+
+/// Converts the cursor of a recently played page into the time limit of the
+/// next request, which is `time_limit` for the first one.
+fn recently_played_time_limit(
+    cursor: Option<String>,
+    direction: CursorDirection,
+    time_limit: Option<TimeLimits>,
+) -> ClientResult<Option<TimeLimits>> {
+    // The cursors are Unix timestamps in milliseconds
+    let cursor = match cursor {
+        Some(cursor) => cursor,
+        None => return Ok(time_limit),
+    };
+    let invalid = || ClientError::Pagination(format!("invalid cursor `{}`", cursor));
+    let millis = cursor.parse().map_err(|_| invalid())?;
+    let date = Utc
+        .timestamp_millis_opt(millis)
+        .single()
+        .ok_or_else(invalid)?;
+    Ok(Some(match direction {
+        CursorDirection::After => TimeLimits::After(date),
+        CursorDirection::Before => TimeLimits::Before(date),
+    }))
 }
@@ -1,30 +1,240 @@
 //! Synchronous implementation of automatic pagination requests.
 
-use crate::{model::Page, ClientError, ClientResult};
+use super::{
+    checkpoint_cursor, checkpoint_offset, cursor_items, is_last_page, offset_items,
+    positioned_page, remaining_offsets, same_kind, CursorDirection, Position, Positioned, Progress,
+};
+use crate::{
+    model::{Checkpoint, CursorBasedPage, Page},
+    ClientError, ClientResult,
+};
 
-/// Alias for `Iterator<Item = T>`, since sync mode is enabled.
-pub type Paginator<'a, T> = Box<dyn Iterator<Item = T> + 'a>;
+use std::{collections::VecDeque, thread};
+
+/// The iterator of items that the paginator restarts from its checkpoint,
+/// along with the position after each of them.
+type PositionedIter<'a, T> = Box<dyn Iterator<Item = (T, Option<Position>)> + Send + 'a>;
+
+/// Creates the iterator of items starting at a checkpoint.
+type Restart<'a, T> = Box<dyn Fn(&Checkpoint) -> PositionedIter<'a, T> + Send + 'a>;
+
+/// Implements `Iterator<Item = T>`, since sync mode is enabled.
+pub struct Paginator<'a, T> {
+    iter: Option<PositionedIter<'a, T>>,
+    restart: Restart<'a, T>,
+    checkpoint: Checkpoint,
+    progress: Progress,
+    done: bool,
+}
+
+impl<'a, T> Paginator<'a, T> {
+    fn new<F>(checkpoint: Checkpoint, restart: F) -> Self
+    where
+        F: Fn(&Checkpoint) -> PositionedIter<'a, T> + Send + 'a,
+    {
+        Self {
+            iter: None,
+            restart: Box::new(restart),
+            checkpoint,
+            progress: Progress {
+                fetched: 0,
+                total: None,
+            },
+            done: false,
+        }
+    }
+
+    /// Returns the position of the next item, which may be saved in order to
+    /// continue the pagination later on with [`Self::resume`].
+    pub fn checkpoint(&self) -> &Checkpoint {
+        &self.checkpoint
+    }
+
+    /// Returns how many items have been fetched, and how many there are in
+    /// total.
+    pub fn progress(&self) -> Progress {
+        self.progress
+    }
+
+    /// Continues the pagination from a checkpoint obtained previously from a
+    /// paginator of the same endpoint and parameters. This is also how a
+    /// request that failed is retried, with the paginator's own checkpoint.
+    ///
+    /// This fails if the checkpoint is of a different kind, e.g. a cursor for
+    /// an offset-based paginator.
+    pub fn resume(mut self, checkpoint: Checkpoint) -> ClientResult<Self> {
+        if !same_kind(&self.checkpoint, &checkpoint) {
+            return Err(ClientError::Pagination(format!(
+                "can't resume this paginator from {:?}",
+                checkpoint
+            )));
+        }
+
+        self.progress = Progress {
+            fetched: match checkpoint {
+                Checkpoint::Offset(offset) => offset,
+                Checkpoint::Cursor { .. } => 0,
+            },
+            total: None,
+        };
+        self.checkpoint = checkpoint;
+        self.iter = None;
+        self.done = false;
+        Ok(self)
+    }
+}
+
+impl<T> Iterator for Paginator<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+
+        let iter = self
+            .iter
+            .get_or_insert_with(|| (self.restart)(&self.checkpoint));
+        match iter.next() {
+            Some((item, Some(position))) => {
+                self.checkpoint = position.checkpoint;
+                self.progress.fetched += position.items;
+                if position.total.is_some() {
+                    self.progress.total = position.total;
+                }
+                Some(item)
+            }
+            // The pagination ends after an error, but it may be retried from
+            // the last checkpoint with `resume`
+            Some((item, None)) => {
+                self.iter = None;
+                self.done = true;
+                Some(item)
+            }
+            None => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
 
 /// This is used to handle paginated requests automatically.
-pub fn paginate<'a, T: 'a, Request: 'a>(
+pub fn paginate<'a, T: 'a + Send, Request: 'a>(
     req: Request,
     page_size: u32,
 ) -> Paginator<'a, ClientResult<T>>
 where
-    Request: Fn(u32, u32) -> ClientResult<Page<T>>,
+    Request: Fn(u32, u32) -> ClientResult<Page<T>> + Send + Clone,
+{
+    Paginator::new(Checkpoint::Offset(0), move |checkpoint| {
+        let pages = PageIterator {
+            req: req.clone(),
+            offset: checkpoint_offset(checkpoint),
+            done: false,
+            page_size,
+        };
+
+        Box::new(pages.flat_map(offset_items))
+    })
+}
+
+/// This is used to handle paginated requests automatically, yielding the
+/// pages themselves instead of their items.
+pub fn paginate_pages<'a, T: 'a + Send, Request: 'a>(
+    req: Request,
+    page_size: u32,
+) -> Paginator<'a, ClientResult<Page<T>>>
+where
+    Request: Fn(u32, u32) -> ClientResult<Page<T>> + Send + Clone,
+{
+    Paginator::new(Checkpoint::Offset(0), move |checkpoint| {
+        let pages = PageIterator {
+            req: req.clone(),
+            offset: checkpoint_offset(checkpoint),
+            done: false,
+            page_size,
+        };
+
+        Box::new(pages.map(positioned_page))
+    })
+}
+
+/// This is used to handle paginated requests automatically, fetching up to
+/// `concurrency` pages at the same time in separate threads. The first page is
+/// requested on its own to find out the total number of items, and the rest
+/// are requested in batches afterwards. The items are still yielded in order.
+///
+/// Each batch has up to `concurrency` pages, which are requested from scoped
+/// threads since `req` may borrow the client. The next batch isn't started
+/// until every page of the current one has been fetched.
+///
+/// With a `concurrency` of 1 this is the same as [`paginate`].
+pub fn paginate_concurrent<'a, T, Request>(
+    req: Request,
+    page_size: u32,
+    concurrency: usize,
+) -> Paginator<'a, ClientResult<T>>
+where
+    T: 'a + Send,
+    Request: 'a + Fn(u32, u32) -> ClientResult<Page<T>> + Send + Sync + Clone,
+{
+    if concurrency <= 1 {
+        return paginate(req, page_size);
+    }
+
+    Paginator::new(Checkpoint::Offset(0), move |checkpoint| {
+        let pages = ConcurrentPageIterator {
+            req: req.clone(),
+            page_size,
+            concurrency,
+            start: checkpoint_offset(checkpoint),
+            offsets: None,
+            fetched: VecDeque::new(),
+            done: false,
+        };
+
+        Box::new(pages.flat_map(offset_items))
+    })
+}
+
+/// This is used to handle cursor-based paginated requests automatically. The
+/// request receives the page size and the cursor of the page to fetch, which
+/// is `None` for the first one.
+pub fn paginate_with_cursor<'a, T: 'a + Send, Request: 'a>(
+    req: Request,
+    page_size: u32,
+    direction: CursorDirection,
+) -> Paginator<'a, ClientResult<T>>
+where
+    Request: Fn(u32, Option<String>) -> ClientResult<CursorBasedPage<T>> + Send + Clone,
 {
-    let pages = PageIterator {
-        req,
-        offset: 0,
-        done: false,
-        page_size,
+    let start = Checkpoint::Cursor {
+        cursor: None,
+        skip: 0,
     };
+    Paginator::new(start, move |checkpoint| {
+        let (cursor, skip) = checkpoint_cursor(checkpoint);
+        let pages = CursorPageIterator {
+            req: req.clone(),
+            cursor,
+            skip,
+            done: false,
+            page_size,
+            direction,
+        };
 
-    Box::new(pages.flat_map(|result| ResultIter::new(result.map(|page| page.items.into_iter()))))
+        Box::new(pages.flat_map(|result| -> Vec<Positioned<T>> {
+            match result {
+                Ok((page, cursor, next, skip)) => cursor_items(page, cursor, next, skip).collect(),
+                Err(e) => vec![(Err(e), None)],
+            }
+        }))
+    })
 }
 
-/// Iterator that repeatedly calls a function that returns a page until an empty
-/// page is returned.
+/// Iterator that repeatedly calls a function that returns a page until the
+/// last one or an error. The pages are returned along with their offset.
 struct PageIterator<Request> {
     req: Request,
     offset: u32,
@@ -36,7 +246,7 @@ impl<T, Request> Iterator for PageIterator<Request>
 where
     Request: Fn(u32, u32) -> ClientResult<Page<T>>,
 {
-    type Item = ClientResult<Page<T>>;
+    type Item = ClientResult<(u32, Page<T>)>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.done {
@@ -44,49 +254,150 @@ where
         }
 
         match (self.req)(self.page_size, self.offset) {
-            Ok(page) if page.items.is_empty() => {
-                self.done = true;
-                None
-            }
             Ok(page) => {
+                let offset = self.offset;
                 self.offset += page.items.len() as u32;
-                Some(Ok(page))
+                self.done = is_last_page(&page, self.offset);
+                Some(Ok((offset, page)))
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
             }
-            Err(e) => Some(Err(e)),
         }
     }
 }
 
-/// Helper to transform a `Result<Iterator<Item = T>, E>` into an `Iterator<Item
-/// = Result<T, E>>`.
-struct ResultIter<T, I: Iterator<Item = T>> {
-    inner: Option<I>,
-    err: Option<ClientError>,
+/// Iterator that requests the first page, and then the rest of them in
+/// batches of concurrent requests, until all of them have been fetched or an
+/// error occurs. The pages are returned along with their offset.
+struct ConcurrentPageIterator<T, Request> {
+    req: Request,
+    page_size: u32,
+    concurrency: usize,
+    /// The offset of the first page
+    start: u32,
+    /// The offsets left to fetch, known after the first page
+    offsets: Option<Box<dyn Iterator<Item = u32> + Send>>,
+    /// The pages fetched in the last batch, yet to be returned
+    fetched: VecDeque<ClientResult<(u32, Page<T>)>>,
+    done: bool,
 }
 
-impl<T, I: Iterator<Item = T>> ResultIter<T, I> {
-    pub fn new(res: ClientResult<I>) -> Self {
-        match res {
-            Ok(inner) => ResultIter {
-                inner: Some(inner),
-                err: None,
-            },
-            Err(err) => ResultIter {
-                inner: None,
-                err: Some(err),
-            },
+impl<T, Request> ConcurrentPageIterator<T, Request>
+where
+    T: Send,
+    Request: Fn(u32, u32) -> ClientResult<Page<T>> + Sync,
+{
+    /// Fetches the next batch of pages, stopping after the first error.
+    fn fetch_batch(&mut self, offsets: Vec<u32>) {
+        let (req, page_size) = (&self.req, self.page_size);
+        let results = thread::scope(|scope| {
+            let handles = offsets
+                .into_iter()
+                .map(|offset| {
+                    scope.spawn(move || req(page_size, offset).map(|page| (offset, page)))
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("pagination thread panicked"))
+                .collect::<Vec<_>>()
+        });
+
+        for result in results {
+            let failed = result.is_err();
+            self.fetched.push_back(result);
+            if failed {
+                self.done = true;
+                break;
+            }
         }
     }
 }
 
-impl<T, I: Iterator<Item = T>> Iterator for ResultIter<T, I> {
-    type Item = ClientResult<T>;
+impl<T, Request> Iterator for ConcurrentPageIterator<T, Request>
+where
+    T: Send,
+    Request: Fn(u32, u32) -> ClientResult<Page<T>> + Sync,
+{
+    type Item = ClientResult<(u32, Page<T>)>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match (self.err.take(), &mut self.inner) {
-            (Some(err), _) => Some(Err(err)),
-            (None, Some(inner)) => inner.next().map(Ok),
-            _ => None, // Error already taken
+        if let Some(page) = self.fetched.pop_front() {
+            return Some(page);
+        }
+        if self.done {
+            return None;
+        }
+
+        match &mut self.offsets {
+            None => {
+                let first = (self.req)(self.page_size, self.start);
+                match &first {
+                    Ok(page) if !is_last_page(page, self.start + page.items.len() as u32) => {
+                        self.offsets = Some(Box::new(remaining_offsets(
+                            self.start,
+                            self.page_size,
+                            page.total,
+                        )));
+                    }
+                    _ => self.done = true,
+                }
+                Some(first.map(|page| (self.start, page)))
+            }
+            Some(offsets) => {
+                let batch = offsets.take(self.concurrency).collect::<Vec<_>>();
+                if batch.is_empty() {
+                    self.done = true;
+                    return None;
+                }
+                self.fetch_batch(batch);
+                self.fetched.pop_front()
+            }
+        }
+    }
+}
+
+/// Iterator that repeatedly calls a function that returns a cursor-based page,
+/// following its cursors until there are no more pages or an error occurs. The
+/// pages are returned along with the cursor used to request them, the cursor
+/// of the next page, and how many of their items to skip.
+struct CursorPageIterator<Request> {
+    req: Request,
+    cursor: Option<String>,
+    skip: u32,
+    done: bool,
+    page_size: u32,
+    direction: CursorDirection,
+}
+
+impl<T, Request> Iterator for CursorPageIterator<Request>
+where
+    Request: Fn(u32, Option<String>) -> ClientResult<CursorBasedPage<T>>,
+{
+    type Item = ClientResult<(CursorBasedPage<T>, Option<String>, Option<String>, u32)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let cursor = self.cursor.take();
+        let skip = std::mem::take(&mut self.skip);
+        match (self.req)(self.page_size, cursor.clone()) {
+            Ok(page) => {
+                let next = self.direction.next_cursor(&page);
+                match &next {
+                    Some(next) => self.cursor = Some(next.clone()),
+                    None => self.done = true,
+                }
+                Some(Ok((page, cursor, next, skip)))
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
         }
     }
 }
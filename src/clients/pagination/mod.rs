@@ -7,6 +7,12 @@
 //! * A `Paginator` struct which wraps the iterable of items
 //! * A `paginate` function, which returns a `Paginator` based on a request that
 //!   may be repeated in order to return a continuous sequence of `Page`s
+//! * A `paginate_pages` function, which is like `paginate` but yields the whole
+//!   `Page`s instead of their items
+//! * A `paginate_concurrent` function, which is like `paginate` but fetches up
+//!   to a number of pages at the same time once the total is known
+//! * A `paginate_with_cursor` function, which does the same for requests that
+//!   return a `CursorBasedPage`, following its cursors instead of an offset
 //!
 //! Note that `Paginator` should actually be a trait so that a dynamic
 //! allocation can be avoided when returning it with `-> impl Iterator<T>`, as
@@ -15,6 +21,17 @@
 //! impossible for now. This is the same small overhead introduced by the
 //! `async_trait` crate and that will hopefully be fixed in the future.
 //!
+//! A `Paginator` keeps track of its position, which can be saved as a
+//! [`Checkpoint`] with `Paginator::checkpoint` and restored with
+//! `Paginator::resume`. When a request fails, the error is yielded and the
+//! pagination ends; it can be retried from the same position by resuming it
+//! from its own checkpoint.
+//!
+//! The offset-based pagination ends after a page without a `next` URL, once
+//! `total` items have been fetched, or after an empty page, regardless of
+//! whether the client is synchronous or asynchronous. The progress of a
+//! `Paginator` can be obtained with `Paginator::progress`.
+//!
 //! Both `Paginator` and `paginate` have a lifetime of `'a`. This is because the
 //! pagination may borrow the client itself in order to make requests, and said
 //! lifetime helps ensure the `Paginator` struct won't outlive the client.
@@ -25,6 +42,486 @@ mod iter;
 mod stream;
 
 #[cfg(feature = "__sync")]
-pub use iter::{paginate, Paginator};
+pub use iter::{paginate, paginate_concurrent, paginate_pages, paginate_with_cursor, Paginator};
 #[cfg(feature = "__async")]
-pub use stream::{paginate, Paginator};
+pub use stream::{paginate, paginate_concurrent, paginate_pages, paginate_with_cursor, Paginator};
+
+use crate::{
+    model::{Checkpoint, CursorBasedPage, Page},
+    ClientResult,
+};
+
+use std::iter::successors;
+
+/// How far a [`Paginator`] has gone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// The number of items fetched so far. For offset-based paginators
+    /// resumed from a checkpoint, this includes the items before it.
+    pub fetched: u32,
+    /// The total number of items, once known. Cursor-based pages only include
+    /// it sometimes.
+    pub total: Option<u32>,
+}
+
+/// The position of the paginator right after one of its items.
+struct Position {
+    checkpoint: Checkpoint,
+    /// The number of items fetched with the yielded item, which is more than
+    /// one for pages.
+    items: u32,
+    total: Option<u32>,
+}
+
+/// An item yielded by the paginator along with its position right after it,
+/// which is `None` for errors.
+type Positioned<T> = (ClientResult<T>, Option<Position>);
+
+/// Whether `page` is the last one, where `end` is the offset right after it.
+fn is_last_page<T>(page: &Page<T>, end: u32) -> bool {
+    page.items.is_empty() || page.next.is_none() || end >= page.total
+}
+
+/// Returns the offsets of the pages after the one starting at `start`, given
+/// the number of items in total.
+fn remaining_offsets(start: u32, page_size: u32, total: u32) -> impl Iterator<Item = u32> {
+    let page_size = page_size.max(1);
+    successors(start.checked_add(page_size), move |offset| {
+        offset.checked_add(page_size)
+    })
+    .take_while(move |offset| *offset < total)
+}
+
+/// Returns the offset in a checkpoint of an offset-based paginator.
+fn checkpoint_offset(checkpoint: &Checkpoint) -> u32 {
+    match checkpoint {
+        Checkpoint::Offset(offset) => *offset,
+        Checkpoint::Cursor { .. } => unreachable!("checked by `Paginator::resume`"),
+    }
+}
+
+/// Returns the cursor and the items to skip in a checkpoint of a cursor-based
+/// paginator.
+fn checkpoint_cursor(checkpoint: &Checkpoint) -> (Option<String>, u32) {
+    match checkpoint {
+        Checkpoint::Cursor { cursor, skip } => (cursor.clone(), *skip),
+        Checkpoint::Offset(_) => unreachable!("checked by `Paginator::resume`"),
+    }
+}
+
+/// Whether a checkpoint can be used to resume a paginator at `current`.
+fn same_kind(current: &Checkpoint, checkpoint: &Checkpoint) -> bool {
+    matches!(
+        (current, checkpoint),
+        (Checkpoint::Offset(_), Checkpoint::Offset(_))
+            | (Checkpoint::Cursor { .. }, Checkpoint::Cursor { .. })
+    )
+}
+
+/// Transforms the result of requesting a page at an offset into its items,
+/// with their positions.
+fn offset_items<T>(result: ClientResult<(u32, Page<T>)>) -> Vec<Positioned<T>> {
+    match result {
+        Ok((offset, page)) => {
+            let total = Some(page.total);
+            page.items
+                .into_iter()
+                .zip(offset + 1..)
+                .map(|(item, next)| {
+                    let position = Position {
+                        checkpoint: Checkpoint::Offset(next),
+                        items: 1,
+                        total,
+                    };
+                    (Ok(item), Some(position))
+                })
+                .collect()
+        }
+        Err(e) => vec![(Err(e), None)],
+    }
+}
+
+/// Transforms the result of requesting a page at an offset into the page,
+/// with its position.
+fn positioned_page<T>(result: ClientResult<(u32, Page<T>)>) -> Positioned<Page<T>> {
+    match result {
+        Ok((offset, page)) => {
+            let items = page.items.len() as u32;
+            let position = Position {
+                checkpoint: Checkpoint::Offset(offset + items),
+                items,
+                total: Some(page.total),
+            };
+            (Ok(page), Some(position))
+        }
+        Err(e) => (Err(e), None),
+    }
+}
+
+/// Returns the items in a cursor-based page requested with `cursor`, with
+/// their positions, except for the first `skip` ones. `next` is the cursor of
+/// the following page, if any.
+fn cursor_items<T>(
+    page: CursorBasedPage<T>,
+    cursor: Option<String>,
+    next: Option<String>,
+    skip: u32,
+) -> impl Iterator<Item = Positioned<T>> {
+    let len = page.items.len() as u32;
+    let total = page.total;
+    page.items
+        .into_iter()
+        .zip(1..)
+        .skip(skip as usize)
+        .map(move |(item, position)| {
+            let checkpoint = match &next {
+                Some(next) if position == len => Checkpoint::Cursor {
+                    cursor: Some(next.clone()),
+                    skip: 0,
+                },
+                _ => Checkpoint::Cursor {
+                    cursor: cursor.clone(),
+                    skip: position,
+                },
+            };
+            let position = Position {
+                checkpoint,
+                items: 1,
+                total,
+            };
+            (Ok(item), Some(position))
+        })
+}
+
+/// The cursor followed by [`paginate_with_cursor`] to request the next page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorDirection {
+    /// Follows `cursors.after`.
+    After,
+    /// Follows `cursors.before`.
+    Before,
+}
+
+impl CursorDirection {
+    /// Returns the cursor for the page after `page`, or `None` if it's the
+    /// last one.
+    fn next_cursor<T>(self, page: &CursorBasedPage<T>) -> Option<String> {
+        if page.items.is_empty() || page.next.is_none() {
+            return None;
+        }
+
+        let cursors = page.cursors.as_ref()?;
+        match self {
+            CursorDirection::After => cursors.after.clone(),
+            CursorDirection::Before => cursors.before.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{model::Cursor, ClientError};
+
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// Returns the page of `items` after the cursor, where the cursor is the
+    /// index of the last item in the previous page.
+    fn cursor_page(items: &[u32], limit: u32, after: Option<String>) -> CursorBasedPage<u32> {
+        let start = after.map_or(0, |after| after.parse::<usize>().unwrap() + 1);
+        let end = items.len().min(start + limit as usize);
+        let last = end < items.len();
+        CursorBasedPage {
+            items: items[start..end].to_vec(),
+            limit,
+            next: last.then(|| "next page".to_owned()),
+            cursors: Some(Cursor {
+                after: last.then(|| (end - 1).to_string()),
+                before: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the page of `items` starting at `offset`.
+    fn offset_page(items: &[u32], limit: u32, offset: u32) -> Page<u32> {
+        let start = items.len().min(offset as usize);
+        let end = items.len().min(start + limit as usize);
+        Page {
+            items: items[start..end].to_vec(),
+            limit,
+            next: (end < items.len()).then(|| "next page".to_owned()),
+            offset,
+            total: items.len() as u32,
+            ..Default::default()
+        }
+    }
+
+    /// Same as `offset_page`, but the first request at offset 10 fails.
+    fn failing_offset_page(
+        items: &[u32],
+        failed: &AtomicBool,
+        limit: u32,
+        offset: u32,
+    ) -> ClientResult<Page<u32>> {
+        if offset == 10 && !failed.swap(true, Ordering::SeqCst) {
+            return Err(ClientError::Pagination("failed request".to_owned()));
+        }
+        Ok(offset_page(items, limit, offset))
+    }
+
+    #[maybe_async::maybe_async]
+    async fn next_item<T>(paginator: &mut Paginator<'_, T>) -> Option<T> {
+        #[cfg(feature = "__async")]
+        {
+            use futures::stream::StreamExt;
+
+            paginator.next().await
+        }
+
+        #[cfg(feature = "__sync")]
+        {
+            paginator.next()
+        }
+    }
+
+    #[maybe_async::maybe_async]
+    async fn collect_items<T>(mut paginator: Paginator<'_, ClientResult<T>>) -> Vec<T> {
+        let mut items = Vec::new();
+        while let Some(item) = next_item(&mut paginator).await {
+            items.push(item.unwrap());
+        }
+        items
+    }
+
+    #[test]
+    fn test_remaining_offsets() {
+        assert_eq!(
+            remaining_offsets(0, 10, 35).collect::<Vec<_>>(),
+            [10, 20, 30]
+        );
+        assert_eq!(remaining_offsets(0, 10, 30).collect::<Vec<_>>(), [10, 20]);
+        assert_eq!(remaining_offsets(5, 10, 30).collect::<Vec<_>>(), [15, 25]);
+        assert_eq!(remaining_offsets(0, 10, 5).count(), 0);
+        assert_eq!(remaining_offsets(0, u32::MAX, u32::MAX).count(), 0);
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_paginate_concurrent() {
+        let items = (0..23).collect::<Vec<u32>>();
+        for concurrency in [1, 2, 4, 10] {
+            let paginator = paginate_concurrent(
+                |limit, offset| {
+                    let page = offset_page(&items, limit, offset);
+                    async move { ClientResult::Ok(page) }
+                },
+                5,
+                concurrency,
+            );
+
+            let fetched = collect_items(paginator).await;
+            assert_eq!(fetched, items);
+        }
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_paginate_pages() {
+        let items = (0..23).collect::<Vec<u32>>();
+        let mut paginator = paginate_pages(
+            |limit, offset| {
+                let page = offset_page(&items, limit, offset);
+                async move { ClientResult::Ok(page) }
+            },
+            5,
+        );
+
+        let mut offsets = Vec::new();
+        while let Some(page) = next_item(&mut paginator).await {
+            let page = page.unwrap();
+            offsets.push(page.offset);
+            let progress = paginator.progress();
+            assert_eq!(progress.fetched, page.offset + page.items.len() as u32);
+            assert_eq!(progress.total, Some(23));
+        }
+        assert_eq!(offsets, [0, 5, 10, 15, 20]);
+        assert_eq!(paginator.checkpoint(), &Checkpoint::Offset(23));
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_paginate_termination() {
+        let items = (0..12).collect::<Vec<u32>>();
+
+        // The pagination ends once `total` items are fetched, even if `next`
+        // is still set
+        let paginator = paginate(
+            |limit, offset| {
+                let mut page = offset_page(&items, limit, offset);
+                page.next = Some("next page".to_owned());
+                async move { ClientResult::Ok(page) }
+            },
+            5,
+        );
+        let fetched = collect_items(paginator).await;
+        assert_eq!(fetched, items);
+
+        // And also after an empty page, even if `total` is larger
+        let paginator = paginate(
+            |limit, offset| {
+                let mut page = offset_page(&items, limit, offset);
+                page.next = Some("next page".to_owned());
+                page.total = 100;
+                async move { ClientResult::Ok(page) }
+            },
+            5,
+        );
+        let fetched = collect_items(paginator).await;
+        assert_eq!(fetched, items);
+
+        // Or after a page without `next`, even if `total` is larger
+        let paginator = paginate(
+            |limit, offset| {
+                let mut page = offset_page(&items, limit, offset);
+                page.total = 100;
+                async move { ClientResult::Ok(page) }
+            },
+            5,
+        );
+        let fetched = collect_items(paginator).await;
+        assert_eq!(fetched, items);
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_paginate_with_cursor() {
+        let items = (0..7).collect::<Vec<u32>>();
+        let paginator = paginate_with_cursor(
+            |limit, after| {
+                let page = cursor_page(&items, limit, after);
+                async move { ClientResult::Ok(page) }
+            },
+            3,
+            CursorDirection::After,
+        );
+
+        let fetched = collect_items(paginator).await;
+        assert_eq!(fetched, items);
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_paginate_resumes_after_error() {
+        let items = (0..23).collect::<Vec<u32>>();
+        for concurrency in [1, 3] {
+            let failed = AtomicBool::new(false);
+            let mut paginator = paginate_concurrent(
+                |limit, offset| {
+                    let page = failing_offset_page(&items, &failed, limit, offset);
+                    async move { page }
+                },
+                5,
+                concurrency,
+            );
+
+            let mut fetched = Vec::new();
+            while let Some(Ok(item)) = next_item(&mut paginator).await {
+                fetched.push(item);
+            }
+            assert_eq!(fetched, (0..10).collect::<Vec<_>>());
+            assert_eq!(paginator.checkpoint(), &Checkpoint::Offset(10));
+            let after_error = next_item(&mut paginator).await;
+            assert!(after_error.is_none());
+
+            // The failed request is retried by resuming the paginator
+            let checkpoint = paginator.checkpoint().clone();
+            let paginator = paginator.resume(checkpoint).unwrap();
+            fetched.extend(collect_items(paginator).await);
+            assert_eq!(fetched, items);
+        }
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_paginate_ends_after_error() {
+        let fail = || ClientError::Pagination("failed request".to_owned());
+
+        for concurrency in [1, 3] {
+            let mut paginator = paginate_concurrent(
+                |_, _| async { ClientResult::<Page<u32>>::Err(fail()) },
+                5,
+                concurrency,
+            );
+            let first = next_item(&mut paginator).await;
+            let second = next_item(&mut paginator).await;
+            let third = next_item(&mut paginator).await;
+            assert!(matches!(first, Some(Err(_))));
+            assert!(second.is_none());
+            assert!(third.is_none());
+        }
+
+        let mut paginator = paginate_with_cursor(
+            |_, _| async { ClientResult::<CursorBasedPage<u32>>::Err(fail()) },
+            3,
+            CursorDirection::After,
+        );
+        let first = next_item(&mut paginator).await;
+        let second = next_item(&mut paginator).await;
+        assert!(matches!(first, Some(Err(_))));
+        assert!(second.is_none());
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_paginate_resume_from_checkpoint() {
+        let items = (0..23).collect::<Vec<u32>>();
+        let request = |limit, offset| {
+            let page = offset_page(&items, limit, offset);
+            async move { ClientResult::Ok(page) }
+        };
+
+        let mut paginator = paginate(request, 5);
+        for _ in 0..7 {
+            next_item(&mut paginator).await.unwrap().unwrap();
+        }
+        let checkpoint = paginator.checkpoint().clone();
+        assert_eq!(checkpoint, Checkpoint::Offset(7));
+
+        // The checkpoint can be saved and used by another paginator
+        let json = serde_json::to_string(&checkpoint).unwrap();
+        let checkpoint = serde_json::from_str(&json).unwrap();
+        let paginator = paginate_concurrent(request, 5, 2)
+            .resume(checkpoint)
+            .unwrap();
+        let fetched = collect_items(paginator).await;
+        assert_eq!(fetched, (7..23).collect::<Vec<_>>());
+
+        let wrong_kind = Checkpoint::Cursor {
+            cursor: None,
+            skip: 0,
+        };
+        assert!(paginate(request, 5).resume(wrong_kind).is_err());
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_paginate_with_cursor_resume_from_checkpoint() {
+        let items = (0..7).collect::<Vec<u32>>();
+        let request = |limit, after| {
+            let page = cursor_page(&items, limit, after);
+            async move { ClientResult::Ok(page) }
+        };
+
+        let mut paginator = paginate_with_cursor(request, 3, CursorDirection::After);
+        for _ in 0..4 {
+            next_item(&mut paginator).await.unwrap().unwrap();
+        }
+        let checkpoint = paginator.checkpoint().clone();
+        assert_eq!(
+            checkpoint,
+            Checkpoint::Cursor {
+                cursor: Some("2".to_owned()),
+                skip: 1
+            }
+        );
+
+        let paginator = paginate_with_cursor(request, 3, CursorDirection::After)
+            .resume(checkpoint)
+            .unwrap();
+        let fetched = collect_items(paginator).await;
+        assert_eq!(fetched, (4..7).collect::<Vec<_>>());
+    }
+}
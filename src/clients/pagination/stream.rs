@@ -1,33 +1,312 @@
 //! Asynchronous implementation of automatic pagination requests.
 
-use crate::{model::Page, ClientResult};
+use super::{
+    checkpoint_cursor, checkpoint_offset, cursor_items, is_last_page, offset_items,
+    positioned_page, remaining_offsets, same_kind, CursorDirection, Position, Progress,
+};
+use crate::{
+    model::{Checkpoint, CursorBasedPage, Page},
+    ClientError, ClientResult,
+};
 
-use std::pin::Pin;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
 
-use futures::{future::Future, stream::Stream};
+use async_stream::stream;
+use futures::{
+    future::Future,
+    stream::{self, Stream, StreamExt},
+};
 
-/// Alias for `futures::stream::Stream<Item = T>`, since async mode is enabled.
-pub type Paginator<'a, T> = Pin<Box<dyn Stream<Item = T> + 'a>>;
+/// The stream of items that the paginator restarts from its checkpoint, along
+/// with the position after each of them.
+type PositionedStream<'a, T> = Pin<Box<dyn Stream<Item = (T, Option<Position>)> + Send + 'a>>;
+
+/// Creates the stream of items starting at a checkpoint.
+type Restart<'a, T> = Box<dyn Fn(&Checkpoint) -> PositionedStream<'a, T> + Send + 'a>;
+
+/// Implements `futures::stream::Stream<Item = T>`, since async mode is
+/// enabled.
+pub struct Paginator<'a, T> {
+    stream: Option<PositionedStream<'a, T>>,
+    restart: Restart<'a, T>,
+    checkpoint: Checkpoint,
+    progress: Progress,
+    done: bool,
+}
+
+impl<'a, T> Paginator<'a, T> {
+    fn new<F>(checkpoint: Checkpoint, restart: F) -> Self
+    where
+        F: Fn(&Checkpoint) -> PositionedStream<'a, T> + Send + 'a,
+    {
+        Self {
+            stream: None,
+            restart: Box::new(restart),
+            checkpoint,
+            progress: Progress {
+                fetched: 0,
+                total: None,
+            },
+            done: false,
+        }
+    }
+
+    /// Returns the position of the next item, which may be saved in order to
+    /// continue the pagination later on with [`Self::resume`].
+    pub fn checkpoint(&self) -> &Checkpoint {
+        &self.checkpoint
+    }
+
+    /// Returns how many items have been fetched, and how many there are in
+    /// total.
+    pub fn progress(&self) -> Progress {
+        self.progress
+    }
+
+    /// Continues the pagination from a checkpoint obtained previously from a
+    /// paginator of the same endpoint and parameters. This is also how a
+    /// request that failed is retried, with the paginator's own checkpoint.
+    ///
+    /// This fails if the checkpoint is of a different kind, e.g. a cursor for
+    /// an offset-based paginator.
+    pub fn resume(mut self, checkpoint: Checkpoint) -> ClientResult<Self> {
+        if !same_kind(&self.checkpoint, &checkpoint) {
+            return Err(ClientError::Pagination(format!(
+                "can't resume this paginator from {:?}",
+                checkpoint
+            )));
+        }
+
+        self.progress = Progress {
+            fetched: match checkpoint {
+                Checkpoint::Offset(offset) => offset,
+                Checkpoint::Cursor { .. } => 0,
+            },
+            total: None,
+        };
+        self.checkpoint = checkpoint;
+        self.stream = None;
+        self.done = false;
+        Ok(self)
+    }
+}
+
+impl<T> Stream for Paginator<'_, T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        let stream = this
+            .stream
+            .get_or_insert_with(|| (this.restart)(&this.checkpoint));
+        match stream.as_mut().poll_next(cx) {
+            Poll::Ready(Some((item, Some(position)))) => {
+                this.checkpoint = position.checkpoint;
+                this.progress.fetched += position.items;
+                if position.total.is_some() {
+                    this.progress.total = position.total;
+                }
+                Poll::Ready(Some(item))
+            }
+            // The pagination ends after an error, but it may be retried from
+            // the last checkpoint with `resume`
+            Poll::Ready(Some((item, None))) => {
+                this.stream = None;
+                this.done = true;
+                Poll::Ready(Some(item))
+            }
+            Poll::Ready(None) => {
+                this.done = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
 
 /// This is used to handle paginated requests automatically.
 pub fn paginate<'a, T, Fut, Request>(req: Request, page_size: u32) -> Paginator<'a, ClientResult<T>>
 where
-    T: 'a + Unpin,
-    Fut: Future<Output = ClientResult<Page<T>>>,
-    Request: 'a + Fn(u32, u32) -> Fut,
+    T: 'a + Unpin + Send,
+    Fut: 'a + Future<Output = ClientResult<Page<T>>> + Send,
+    Request: 'a + Fn(u32, u32) -> Fut + Send + Clone,
 {
-    use async_stream::stream;
-    let mut offset = 0;
-    Box::pin(stream! {
+    Paginator::new(Checkpoint::Offset(0), move |checkpoint| {
+        let pages = offset_pages(req.clone(), page_size, checkpoint_offset(checkpoint));
+        Box::pin(pages.flat_map(|result| stream::iter(offset_items(result))))
+    })
+}
+
+/// This is used to handle paginated requests automatically, yielding the
+/// pages themselves instead of their items.
+pub fn paginate_pages<'a, T, Fut, Request>(
+    req: Request,
+    page_size: u32,
+) -> Paginator<'a, ClientResult<Page<T>>>
+where
+    T: 'a + Unpin + Send,
+    Fut: 'a + Future<Output = ClientResult<Page<T>>> + Send,
+    Request: 'a + Fn(u32, u32) -> Fut + Send + Clone,
+{
+    Paginator::new(Checkpoint::Offset(0), move |checkpoint| {
+        let pages = offset_pages(req.clone(), page_size, checkpoint_offset(checkpoint));
+        Box::pin(pages.map(positioned_page))
+    })
+}
+
+/// This is used to handle paginated requests automatically, fetching up to
+/// `concurrency` pages at the same time. The first page is requested on its
+/// own to find out the total number of items, and the rest are requested
+/// concurrently afterwards. The items are still yielded in order.
+///
+/// With a `concurrency` of 1 this is the same as [`paginate`].
+pub fn paginate_concurrent<'a, T, Fut, Request>(
+    req: Request,
+    page_size: u32,
+    concurrency: usize,
+) -> Paginator<'a, ClientResult<T>>
+where
+    T: 'a + Unpin + Send,
+    Fut: 'a + Future<Output = ClientResult<Page<T>>> + Send,
+    Request: 'a + Fn(u32, u32) -> Fut + Send + Sync + Clone,
+{
+    if concurrency <= 1 {
+        return paginate(req, page_size);
+    }
+
+    Paginator::new(Checkpoint::Offset(0), move |checkpoint| {
+        let start = checkpoint_offset(checkpoint);
+        let pages = concurrent_offset_pages(req.clone(), page_size, start, concurrency);
+        Box::pin(pages.flat_map(|result| stream::iter(offset_items(result))))
+    })
+}
+
+/// This is used to handle cursor-based paginated requests automatically. The
+/// request receives the page size and the cursor of the page to fetch, which
+/// is `None` for the first one.
+pub fn paginate_with_cursor<'a, T, Fut, Request>(
+    req: Request,
+    page_size: u32,
+    direction: CursorDirection,
+) -> Paginator<'a, ClientResult<T>>
+where
+    T: 'a + Unpin + Send,
+    Fut: 'a + Future<Output = ClientResult<CursorBasedPage<T>>> + Send,
+    Request: 'a + Fn(u32, Option<String>) -> Fut + Send + Clone,
+{
+    let start = Checkpoint::Cursor {
+        cursor: None,
+        skip: 0,
+    };
+    Paginator::new(start, move |checkpoint| {
+        let (mut cursor, mut skip) = checkpoint_cursor(checkpoint);
+        let req = req.clone();
+        Box::pin(stream! {
+            loop {
+                let page = match req(page_size, cursor.clone()).await {
+                    Ok(page) => page,
+                    Err(e) => {
+                        yield (Err(e), None);
+                        break;
+                    }
+                };
+                let next = direction.next_cursor(&page);
+                for positioned in cursor_items(page, cursor.take(), next.clone(), skip) {
+                    yield positioned;
+                }
+                skip = 0;
+                match next {
+                    Some(next) => cursor = Some(next),
+                    None => break,
+                }
+            }
+        })
+    })
+}
+
+/// Requests the pages one after another starting at `offset`, until the last
+/// one or an error. They are returned along with their offset.
+fn offset_pages<'a, T, Fut, Request>(
+    req: Request,
+    page_size: u32,
+    mut offset: u32,
+) -> impl Stream<Item = ClientResult<(u32, Page<T>)>> + 'a
+where
+    T: 'a + Send,
+    Fut: 'a + Future<Output = ClientResult<Page<T>>> + Send,
+    Request: 'a + Fn(u32, u32) -> Fut + Send,
+{
+    stream! {
         loop {
-            let page = req(page_size, offset).await?;
-            offset += page.items.len() as u32;
-            for item in page.items {
-                yield Ok(item);
+            match req(page_size, offset).await {
+                Ok(page) => {
+                    let start = offset;
+                    offset += page.items.len() as u32;
+                    let last = is_last_page(&page, offset);
+                    yield Ok((start, page));
+                    if last {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    yield Err(e);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Requests the page at `start`, and then the rest of them concurrently,
+/// until the last one or an error. They are returned in order, along with
+/// their offset.
+fn concurrent_offset_pages<'a, T, Fut, Request>(
+    req: Request,
+    page_size: u32,
+    start: u32,
+    concurrency: usize,
+) -> impl Stream<Item = ClientResult<(u32, Page<T>)>> + 'a
+where
+    T: 'a + Send,
+    Fut: 'a + Future<Output = ClientResult<Page<T>>> + Send,
+    Request: 'a + Fn(u32, u32) -> Fut + Send + Sync,
+{
+    stream! {
+        let first = match req(page_size, start).await {
+            Ok(page) => page,
+            Err(e) => {
+                yield Err(e);
+                return;
             }
-            if page.next.is_none() {
-                break;
+        };
+        let total = first.total;
+        let last = is_last_page(&first, start + first.items.len() as u32);
+        yield Ok((start, first));
+        if last {
+            return;
+        }
+
+        let mut pages = stream::iter(remaining_offsets(start, page_size, total))
+            .map(|offset| {
+                let page = req(page_size, offset);
+                async move { (offset, page.await) }
+            })
+            .buffered(concurrency);
+        while let Some((offset, page)) = pages.next().await {
+            match page {
+                Ok(page) => yield Ok((offset, page)),
+                Err(e) => {
+                    yield Err(e);
+                    return;
+                }
             }
         }
-    })
+    }
 }
@@ -0,0 +1,105 @@
+//! Requests to any endpoint of the Spotify API, including the ones that
+//! aren't wrapped by the clients yet.
+
+use serde_json::{json, Value};
+
+/// The HTTP method of an [`ApiRequest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+/// A request to an endpoint of the Spotify API, which can be sent with
+/// [`BaseClient::api_request`](crate::clients::BaseClient::api_request).
+///
+/// ```
+/// use rspotify::clients::ApiRequest;
+///
+/// let request = ApiRequest::get("me/player/queue").query("market", "ES");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiRequest {
+    pub method: Method,
+    /// Relative to the configured API prefix, or an absolute URL.
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    /// Ignored in `GET` requests.
+    pub body: Value,
+}
+
+impl ApiRequest {
+    /// Creates a request with the given method to `path`, which may be
+    /// relative to the configured API prefix or an absolute URL.
+    pub fn new(method: Method, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            query: Vec::new(),
+            body: json!({}),
+        }
+    }
+
+    /// Creates a `GET` request to `path`.
+    pub fn get(path: impl Into<String>) -> Self {
+        Self::new(Method::Get, path)
+    }
+
+    /// Creates a `POST` request to `path`.
+    pub fn post(path: impl Into<String>) -> Self {
+        Self::new(Method::Post, path)
+    }
+
+    /// Creates a `PUT` request to `path`.
+    pub fn put(path: impl Into<String>) -> Self {
+        Self::new(Method::Put, path)
+    }
+
+    /// Creates a `DELETE` request to `path`.
+    pub fn delete(path: impl Into<String>) -> Self {
+        Self::new(Method::Delete, path)
+    }
+
+    /// Sets a query parameter, replacing its previous value if any.
+    #[must_use]
+    pub fn query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        let (key, value) = (key.into(), value.into());
+        match self.query.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = value,
+            None => self.query.push((key, value)),
+        }
+        self
+    }
+
+    /// Sets the JSON body, which is an empty object by default.
+    #[must_use]
+    pub fn body(mut self, body: Value) -> Self {
+        self.body = body;
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_api_request_query() {
+        let request = ApiRequest::get("browse/categories")
+            .query("limit", "10")
+            .query("locale", "es_ES")
+            .query("limit", "20");
+        assert_eq!(request.method, Method::Get);
+        assert_eq!(request.path, "browse/categories");
+        assert_eq!(
+            request.query,
+            [
+                ("limit".to_owned(), "20".to_owned()),
+                ("locale".to_owned(), "es_ES".to_owned())
+            ]
+        );
+        assert_eq!(request.body, json!({}));
+    }
+}
@@ -0,0 +1,381 @@
+//! Layered configuration, read from a file in the user's configuration
+//! directory and then overridden by the environment variables.
+//!
+//! Note: this module requires the `config-file` feature.
+
+use crate::{ClientError, ClientResult, Config, Credentials, OAuth};
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+/// The name of the directory inside the user's configuration directory where
+/// the configuration file is looked up.
+pub const CONFIG_FILE_DIR: &str = "rspotify";
+
+/// The configuration for [`Credentials`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CredentialsSection {
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+/// The configuration for [`OAuth`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OAuthSection {
+    pub redirect_uri: Option<String>,
+    pub scopes: Option<Vec<String>>,
+}
+
+/// The configuration for [`Config`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigSection {
+    pub prefix: Option<String>,
+    pub cache_path: Option<PathBuf>,
+    pub pagination_chunks: Option<u32>,
+    pub token_cached: Option<bool>,
+    pub token_refreshing: Option<bool>,
+}
+
+/// The contents of a configuration file, shared by all the applications using
+/// RSpotify for the same user.
+///
+/// It's looked up in the `rspotify` directory inside the user's configuration
+/// directory (e.g. `$XDG_CONFIG_HOME/rspotify/config.toml` on Linux), either
+/// as `config.toml` or `config.json`. Its path can also be specified with the
+/// `RSPOTIFY_CONFIG_FILE` environment variable.
+///
+/// All the fields are optional, and the environment variables take priority
+/// over them:
+///
+/// ```toml
+/// [credentials]
+/// client_id = "..."          # RSPOTIFY_CLIENT_ID
+/// client_secret = "..."      # RSPOTIFY_CLIENT_SECRET
+///
+/// [oauth]
+/// redirect_uri = "http://localhost:8888/callback"   # RSPOTIFY_REDIRECT_URI
+/// scopes = ["user-read-private"]                    # RSPOTIFY_SCOPES, space separated
+///
+/// [config]
+/// prefix = "https://api.spotify.com/v1/"   # RSPOTIFY_PREFIX
+/// cache_path = ".spotify_token_cache.json" # RSPOTIFY_CACHE_PATH
+/// pagination_chunks = 50                   # RSPOTIFY_PAGINATION_CHUNKS
+/// token_cached = true                      # RSPOTIFY_TOKEN_CACHED
+/// token_refreshing = true                  # RSPOTIFY_TOKEN_REFRESHING
+/// ```
+///
+/// Sample usage:
+///
+/// ```no_run
+/// use rspotify::{AuthCodeSpotify, ConfigFile};
+///
+/// let file = ConfigFile::load().unwrap();
+/// let creds = file.credentials().unwrap();
+/// let oauth = file.oauth().unwrap();
+/// let spotify = AuthCodeSpotify::with_config(creds, oauth, file.config());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    pub credentials: CredentialsSection,
+    pub oauth: OAuthSection,
+    pub config: ConfigSection,
+}
+
+impl ConfigFile {
+    /// Reads the configuration file from its default location, if it exists,
+    /// and then overrides it with the environment variables. You can
+    /// optionally activate the `env-file` feature in order to read these
+    /// variables from a `.env` file.
+    pub fn load() -> ClientResult<Self> {
+        #[cfg(feature = "env-file")]
+        {
+            dotenv::dotenv().ok();
+        }
+
+        let mut file = match Self::default_path() {
+            Some(path) => Self::from_path(path)?,
+            None => Self::default(),
+        };
+        file.override_with_env()?;
+
+        Ok(file)
+    }
+
+    /// Returns the path of the configuration file, if it exists. This is the
+    /// value of `RSPOTIFY_CONFIG_FILE` if set, or `config.toml` or
+    /// `config.json` in the [`CONFIG_FILE_DIR`] directory inside the user's
+    /// configuration directory.
+    #[must_use]
+    pub fn default_path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os("RSPOTIFY_CONFIG_FILE") {
+            return Some(PathBuf::from(path));
+        }
+
+        let dir = dirs::config_dir()?.join(CONFIG_FILE_DIR);
+        ["config.toml", "config.json"]
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.exists())
+    }
+
+    /// Reads the configuration file at the given path. It's parsed as JSON if
+    /// its extension is `json`, and as TOML otherwise.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> ClientResult<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+
+        let is_json = path.extension().map_or(false, |ext| ext == "json");
+        let file = if is_json {
+            serde_json::from_str(&contents)
+                .map_err(|e| ClientError::ConfigFile(format!("{}: {}", path.display(), e)))?
+        } else {
+            toml::from_str(&contents)
+                .map_err(|e| ClientError::ConfigFile(format!("{}: {}", path.display(), e)))?
+        };
+
+        Ok(file)
+    }
+
+    /// Overrides the configuration with the `RSPOTIFY_*` environment variables
+    /// that are set.
+    pub fn override_with_env(&mut self) -> ClientResult<()> {
+        self.override_with(|name| env::var(name).ok())
+    }
+
+    /// Overrides the configuration with the variables returned by `var`.
+    fn override_with<F: Fn(&str) -> Option<String>>(&mut self, var: F) -> ClientResult<()> {
+        let parse_bool = |name: &str| -> ClientResult<Option<bool>> {
+            var(name)
+                .map(|val| match val.to_lowercase().as_str() {
+                    "1" | "true" | "yes" => Ok(true),
+                    "0" | "false" | "no" => Ok(false),
+                    _ => Err(ClientError::ConfigFile(format!(
+                        "{} must be a boolean, found `{}`",
+                        name, val
+                    ))),
+                })
+                .transpose()
+        };
+
+        if let Some(id) = var("RSPOTIFY_CLIENT_ID") {
+            self.credentials.client_id = Some(id);
+        }
+        if let Some(secret) = var("RSPOTIFY_CLIENT_SECRET") {
+            self.credentials.client_secret = Some(secret);
+        }
+
+        if let Some(redirect_uri) = var("RSPOTIFY_REDIRECT_URI") {
+            self.oauth.redirect_uri = Some(redirect_uri);
+        }
+        if let Some(scopes) = var("RSPOTIFY_SCOPES") {
+            self.oauth.scopes = Some(scopes.split_whitespace().map(ToOwned::to_owned).collect());
+        }
+
+        if let Some(prefix) = var("RSPOTIFY_PREFIX") {
+            self.config.prefix = Some(prefix);
+        }
+        if let Some(cache_path) = var("RSPOTIFY_CACHE_PATH") {
+            self.config.cache_path = Some(PathBuf::from(cache_path));
+        }
+        if let Some(chunks) = var("RSPOTIFY_PAGINATION_CHUNKS") {
+            let chunks = chunks.parse().map_err(|_| {
+                ClientError::ConfigFile(format!(
+                    "RSPOTIFY_PAGINATION_CHUNKS must be a number, found `{}`",
+                    chunks
+                ))
+            })?;
+            self.config.pagination_chunks = Some(chunks);
+        }
+        if let Some(cached) = parse_bool("RSPOTIFY_TOKEN_CACHED")? {
+            self.config.token_cached = Some(cached);
+        }
+        if let Some(refreshing) = parse_bool("RSPOTIFY_TOKEN_REFRESHING")? {
+            self.config.token_refreshing = Some(refreshing);
+        }
+
+        Ok(())
+    }
+
+    /// Builds the [`Credentials`], which requires at least the client ID.
+    #[must_use]
+    pub fn credentials(&self) -> Option<Credentials> {
+        Some(Credentials {
+            id: self.credentials.client_id.clone()?,
+            secret: self.credentials.client_secret.clone(),
+        })
+    }
+
+    /// Builds the [`OAuth`] information, which requires at least the redirect
+    /// URI.
+    #[must_use]
+    pub fn oauth(&self) -> Option<OAuth> {
+        Some(OAuth {
+            redirect_uri: self.oauth.redirect_uri.clone()?,
+            scopes: self.oauth.scopes.iter().flatten().cloned().collect(),
+            ..Default::default()
+        })
+    }
+
+    /// Builds the [`Config`], where the missing values are left as their
+    /// default.
+    #[must_use]
+    pub fn config(&self) -> Config {
+        let mut config = Config::default();
+        let section = &self.config;
+        if let Some(prefix) = &section.prefix {
+            config.prefix = prefix.clone();
+        }
+        if let Some(cache_path) = &section.cache_path {
+            config.cache_path = cache_path.clone();
+        }
+        if let Some(chunks) = section.pagination_chunks {
+            config.pagination_chunks = chunks;
+        }
+        if let Some(cached) = section.token_cached {
+            config.token_cached = cached;
+        }
+        if let Some(refreshing) = section.token_refreshing {
+            config.token_refreshing = refreshing;
+        }
+
+        config
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scopes;
+
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_toml_file() {
+        let path = PathBuf::from(".test_config_file.toml");
+        fs::write(
+            &path,
+            r#"
+            [credentials]
+            client_id = "file-id"
+            client_secret = "file-secret"
+
+            [oauth]
+            redirect_uri = "http://localhost:8888/callback"
+            scopes = ["user-read-private", "playlist-read-private"]
+
+            [config]
+            pagination_chunks = 20
+            token_cached = true
+            "#,
+        )
+        .unwrap();
+        let file = ConfigFile::from_path(&path);
+        fs::remove_file(&path).unwrap();
+        let file = file.unwrap();
+
+        let creds = file.credentials().unwrap();
+        assert_eq!(creds.id, "file-id");
+        assert_eq!(creds.secret.unwrap(), "file-secret");
+
+        let oauth = file.oauth().unwrap();
+        assert_eq!(oauth.redirect_uri, "http://localhost:8888/callback");
+        assert_eq!(
+            oauth.scopes,
+            scopes!("user-read-private", "playlist-read-private")
+        );
+
+        let config = file.config();
+        assert_eq!(config.pagination_chunks, 20);
+        assert!(config.token_cached);
+        assert!(!config.token_refreshing);
+        assert_eq!(config.prefix, crate::DEFAULT_API_PREFIX);
+    }
+
+    #[test]
+    fn test_json_file() {
+        let path = PathBuf::from(".test_config_file.json");
+        fs::write(
+            &path,
+            r#"{"credentials": {"client_id": "file-id"}, "config": {"token_refreshing": true}}"#,
+        )
+        .unwrap();
+        let file = ConfigFile::from_path(&path);
+        fs::remove_file(&path).unwrap();
+        let file = file.unwrap();
+
+        let creds = file.credentials().unwrap();
+        assert_eq!(creds.id, "file-id");
+        assert!(creds.secret.is_none());
+        assert!(file.oauth().is_none());
+        assert!(file.config().token_refreshing);
+    }
+
+    #[test]
+    fn test_unknown_field() {
+        let path = PathBuf::from(".test_config_file_unknown.toml");
+        fs::write(&path, "[credentials]\nclient_idd = \"typo\"\n").unwrap();
+        let file = ConfigFile::from_path(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(file, Err(ClientError::ConfigFile(_))));
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let mut file = ConfigFile {
+            credentials: CredentialsSection {
+                client_id: Some("file-id".to_owned()),
+                client_secret: Some("file-secret".to_owned()),
+            },
+            config: ConfigSection {
+                pagination_chunks: Some(20),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let vars = [
+            ("RSPOTIFY_CLIENT_ID", "env-id"),
+            ("RSPOTIFY_REDIRECT_URI", "http://localhost/callback"),
+            ("RSPOTIFY_SCOPES", "user-read-private  user-follow-read"),
+            ("RSPOTIFY_TOKEN_CACHED", "true"),
+        ]
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+        file.override_with(|name| vars.get(name).map(|val| val.to_string()))
+            .unwrap();
+
+        let creds = file.credentials().unwrap();
+        assert_eq!(creds.id, "env-id");
+        assert_eq!(creds.secret.unwrap(), "file-secret");
+        let oauth = file.oauth().unwrap();
+        assert_eq!(oauth.redirect_uri, "http://localhost/callback");
+        assert_eq!(
+            oauth.scopes,
+            scopes!("user-read-private", "user-follow-read")
+        );
+        let config = file.config();
+        assert_eq!(config.pagination_chunks, 20);
+        assert!(config.token_cached);
+    }
+
+    #[test]
+    fn test_invalid_env() {
+        let mut file = ConfigFile::default();
+        let result = file.override_with(|name| {
+            (name == "RSPOTIFY_PAGINATION_CHUNKS").then(|| "many".to_owned())
+        });
+        assert!(matches!(result, Err(ClientError::ConfigFile(_))));
+
+        let result =
+            file.override_with(|name| (name == "RSPOTIFY_TOKEN_CACHED").then(|| "sí".to_owned()));
+        assert!(matches!(result, Err(ClientError::ConfigFile(_))));
+    }
+}
@@ -70,6 +70,31 @@
 //! prompting the user, after enabling the `cli` feature. See the
 //! [Authorization](#authorization) section for more information.
 //!
+//! ### Configuration file
+//!
+//! After enabling the `config-file` feature, the credentials and the
+//! configuration can also be read from a TOML or JSON file in the user's
+//! configuration directory, shared by all the applications using RSpotify.
+//! The environment variables take priority over it. See [`ConfigFile`] for
+//! more information.
+//!
+//! ```toml
+//! [dependencies]
+//! rspotify = { version = "...", features = ["config-file"] }
+//! ```
+//!
+//! ### Encrypted token cache
+//!
+//! The token cache is saved in plaintext by default. After enabling the
+//! `encrypted-cache` feature, you may set `Config::cache_key` so that it's
+//! encrypted at rest instead, with either a key of your own or one derived
+//! from a passphrase.
+//!
+//! ```toml
+//! [dependencies]
+//! rspotify = { version = "...", features = ["encrypted-cache"] }
+//! ```
+//!
 //! ## Getting Started
 //!
 //! ### Authorization
@@ -125,6 +150,8 @@ mod auth_code;
 mod auth_code_pkce;
 mod client_creds;
 pub mod clients;
+#[cfg(feature = "config-file")]
+mod config_file;
 pub mod sync;
 mod util;
 
@@ -136,10 +163,17 @@ pub use rspotify_model as model;
 pub use auth_code::AuthCodeSpotify;
 pub use auth_code_pkce::AuthCodePkceSpotify;
 pub use client_creds::ClientCredsSpotify;
+#[cfg(feature = "config-file")]
+pub use config_file::{
+    ConfigFile, ConfigSection, CredentialsSection, OAuthSection, CONFIG_FILE_DIR,
+};
 pub use macros::scopes;
 pub use model::Token;
 
-use crate::{http::HttpError, model::Id};
+use crate::{
+    http::{HttpClient, HttpError, RetryPolicy},
+    model::Id,
+};
 
 use std::{
     collections::{HashMap, HashSet},
@@ -152,7 +186,7 @@ use thiserror::Error;
 
 pub mod prelude {
     pub use crate::clients::{BaseClient, OAuthClient};
-    pub use crate::model::idtypes::{Id, PlayContextId, PlayableId};
+    pub use crate::model::idtypes::{AnyId, Id, PlayContextId, PlayableId};
 }
 
 /// Common headers as constants.
@@ -214,8 +248,20 @@ pub enum ClientError {
     #[error("cache file error: {0}")]
     CacheFile(String),
 
+    #[error("pagination error: {0}")]
+    Pagination(String),
+
+    #[cfg(feature = "config-file")]
+    #[error("config file error: {0}")]
+    ConfigFile(String),
+
     #[error("model error: {0}")]
     Model(#[from] model::ModelError),
+
+    /// The image to upload is larger than the limit of the API once encoded,
+    /// which is 256 KB.
+    #[error("image too large: {0} bytes once encoded")]
+    ImageTooLarge(usize),
 }
 
 // The conversion has to be done manually because it's in a `Box<T>`
@@ -230,8 +276,50 @@ pub type ClientResult<T> = Result<T, ClientError>;
 pub const DEFAULT_API_PREFIX: &str = "https://api.spotify.com/v1/";
 pub const DEFAULT_CACHE_PATH: &str = ".spotify_token_cache.json";
 pub const DEFAULT_PAGINATION_CHUNKS: u32 = 50;
+pub const DEFAULT_PAGINATION_CONCURRENCY: usize = 1;
+pub const DEFAULT_CHUNK_CONCURRENCY: usize = 1;
+pub const DEFAULT_TOKEN_EXPIRY_MARGIN_SECS: i64 = 10;
+/// The maximum number of items the Spotify API returns in a single page for
+/// most of its endpoints.
+pub const MAX_PAGINATION_CHUNKS: u32 = 50;
+
+/// Errors found when validating a [`Config`] with [`ConfigBuilder::build`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ConfigError {
+    #[error("invalid API prefix `{0}`: it must be an absolute http or https URL")]
+    InvalidPrefix(String),
+
+    #[error(
+        "invalid pagination chunks {0}: it must be between 1 and {}",
+        MAX_PAGINATION_CHUNKS
+    )]
+    InvalidPaginationChunks(u32),
+
+    #[error("invalid pagination concurrency: it can't be zero")]
+    ZeroPaginationConcurrency,
+
+    #[error("invalid chunk concurrency: it can't be zero")]
+    ZeroChunkConcurrency,
+
+    #[error("invalid token expiry margin {0}: it can't be negative")]
+    InvalidTokenExpiryMargin(chrono::Duration),
+
+    #[error("invalid timeout: it can't be zero")]
+    ZeroTimeout,
+
+    #[error(
+        "invalid retry policy: the base delay {base_delay:?} is longer than the maximum delay {max_delay:?}"
+    )]
+    InvalidRetryDelay {
+        base_delay: std::time::Duration,
+        max_delay: std::time::Duration,
+    },
+}
 
 /// Struct to configure the Spotify client.
+///
+/// Its fields may be set directly, but [`Config::builder`] is preferred, since
+/// it validates them before the client is created.
 #[derive(Debug, Clone)]
 pub struct Config {
     /// The Spotify API prefix, [`DEFAULT_API_PREFIX`] by default.
@@ -250,6 +338,26 @@ pub struct Config {
     /// request, which most times is 50.
     pub pagination_chunks: u32,
 
+    /// The maximum number of pages requested at the same time by the
+    /// automatically paginated endpoints. Once the first page reveals the
+    /// total number of items, the rest of them are fetched concurrently, while
+    /// still being yielded in order. By default this is
+    /// [`DEFAULT_PAGINATION_CONCURRENCY`], so pages are fetched one at a time.
+    ///
+    /// With the `client-ureq` feature, each concurrent request is made from a
+    /// separate thread.
+    pub pagination_concurrency: usize,
+
+    /// The maximum number of requests made at the same time by the endpoints
+    /// that take more IDs than the API accepts at once, which split them into
+    /// chunks. See [`clients`](crate::clients#limits-of-the-bulk-endpoints)
+    /// for more information. By default this is [`DEFAULT_CHUNK_CONCURRENCY`],
+    /// so the chunks are requested one at a time.
+    ///
+    /// The chunks are only requested concurrently in async mode; with the
+    /// `client-ureq` feature they're always requested one at a time.
+    pub chunk_concurrency: usize,
+
     /// Whether or not to save the authentication token into a JSON file,
     /// then reread the token from JSON file when launching the program without
     /// following the full auth process again
@@ -258,6 +366,40 @@ pub struct Config {
     /// Whether or not to check if the token has expired when sending a
     /// request with credentials, and in that case, automatically refresh it.
     pub token_refreshing: bool,
+
+    /// How long before its expiration a token is already considered expired,
+    /// which should be at least how long a request may take. Devices whose
+    /// clock drifts may need a larger value. By default this is
+    /// [`DEFAULT_TOKEN_EXPIRY_MARGIN_SECS`] seconds.
+    ///
+    /// The expiration date of the tokens is on Spotify's clock, according to
+    /// the `Date` header of the token endpoint's responses, and it's compared
+    /// with the local time converted with [`Token::clock_skew`].
+    pub token_expiry_margin: chrono::Duration,
+
+    /// The token swap service used by [`AuthCodeSpotify`] to request and
+    /// refresh tokens instead of Spotify's token endpoint, so that the client
+    /// secret isn't needed. See [`TokenSwap`] for more information. By default
+    /// this is `None`.
+    pub token_swap: Option<TokenSwap>,
+
+    /// The key used to encrypt the token cache, if any. Plaintext caches can
+    /// still be read when it's set, and they will be encrypted the next time
+    /// the cache is written. By default this is `None`.
+    #[cfg(feature = "encrypted-cache")]
+    pub cache_key: Option<model::CacheKey>,
+
+    /// The timeout for each HTTP request, or `None` for no timeout, which is
+    /// the default.
+    ///
+    /// Like [`Config::retry_policy`], this is applied when the client is
+    /// created, so modifying it afterwards has no effect.
+    pub timeout: Option<std::time::Duration>,
+
+    /// How the requests that are rate limited or fail because of a server
+    /// error are retried, the latter only for `GET` requests. By default they
+    /// aren't.
+    pub retry_policy: RetryPolicy,
 }
 
 impl Default for Config {
@@ -266,8 +408,253 @@ impl Default for Config {
             prefix: String::from(DEFAULT_API_PREFIX),
             cache_path: PathBuf::from(DEFAULT_CACHE_PATH),
             pagination_chunks: DEFAULT_PAGINATION_CHUNKS,
+            pagination_concurrency: DEFAULT_PAGINATION_CONCURRENCY,
+            chunk_concurrency: DEFAULT_CHUNK_CONCURRENCY,
             token_cached: false,
             token_refreshing: false,
+            token_expiry_margin: chrono::Duration::seconds(DEFAULT_TOKEN_EXPIRY_MARGIN_SECS),
+            token_swap: None,
+            #[cfg(feature = "encrypted-cache")]
+            cache_key: None,
+            timeout: None,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Returns a builder that validates the configuration, starting from the
+    /// default values.
+    #[must_use]
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+
+    /// Initializes the HTTP client with the timeout and retry policy in the
+    /// configuration.
+    pub(in crate) fn http_client(&self) -> HttpClient {
+        HttpClient::with_options(self.timeout, self.retry_policy)
+    }
+
+    /// Reads the token from the cache file, decrypting it if a key has been
+    /// configured.
+    pub(in crate) fn read_cache(&self) -> ClientResult<Token> {
+        #[cfg(feature = "encrypted-cache")]
+        if let Some(key) = &self.cache_key {
+            return Ok(Token::from_encrypted_cache(&self.cache_path, key)?);
+        }
+
+        Ok(Token::from_cache(&self.cache_path)?)
+    }
+
+    /// Writes the token into the cache file, encrypting it if a key has been
+    /// configured.
+    pub(in crate) fn write_cache(&self, token: &Token) -> ClientResult<()> {
+        #[cfg(feature = "encrypted-cache")]
+        if let Some(key) = &self.cache_key {
+            return Ok(token.write_encrypted_cache(&self.cache_path, key)?);
+        }
+
+        Ok(token.write_cache(&self.cache_path)?)
+    }
+
+    /// Removes the token from the cache file.
+    pub(in crate) fn remove_cache(&self) -> ClientResult<()> {
+        Ok(Token::remove_cache(&self.cache_path)?)
+    }
+}
+
+/// Builder for [`Config`], created with [`Config::builder`].
+///
+/// The configuration is validated by [`ConfigBuilder::build`], so that
+/// mistakes are reported before any request is made:
+///
+/// ```
+/// use rspotify::{Config, ConfigError};
+///
+/// let config = Config::builder()
+///     .prefix("http://localhost:8080/v1")
+///     .pagination_chunks(20)
+///     .build()
+///     .unwrap();
+/// assert_eq!(config.prefix, "http://localhost:8080/v1/");
+///
+/// let err = Config::builder().pagination_chunks(0).build().unwrap_err();
+/// assert_eq!(err, ConfigError::InvalidPaginationChunks(0));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    /// Sets [`Config::prefix`]. A trailing slash is added if missing.
+    #[must_use]
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.config.prefix = prefix.into();
+        self
+    }
+
+    /// Sets [`Config::cache_path`].
+    #[must_use]
+    pub fn cache_path(mut self, cache_path: impl Into<PathBuf>) -> Self {
+        self.config.cache_path = cache_path.into();
+        self
+    }
+
+    /// Sets [`Config::pagination_chunks`], which must be between 1 and
+    /// [`MAX_PAGINATION_CHUNKS`].
+    #[must_use]
+    pub fn pagination_chunks(mut self, pagination_chunks: u32) -> Self {
+        self.config.pagination_chunks = pagination_chunks;
+        self
+    }
+
+    /// Sets [`Config::pagination_concurrency`], which can't be zero.
+    #[must_use]
+    pub fn pagination_concurrency(mut self, pagination_concurrency: usize) -> Self {
+        self.config.pagination_concurrency = pagination_concurrency;
+        self
+    }
+
+    /// Sets [`Config::chunk_concurrency`], which can't be zero.
+    #[must_use]
+    pub fn chunk_concurrency(mut self, chunk_concurrency: usize) -> Self {
+        self.config.chunk_concurrency = chunk_concurrency;
+        self
+    }
+
+    /// Sets [`Config::token_cached`].
+    #[must_use]
+    pub fn token_cached(mut self, token_cached: bool) -> Self {
+        self.config.token_cached = token_cached;
+        self
+    }
+
+    /// Sets [`Config::token_refreshing`].
+    #[must_use]
+    pub fn token_refreshing(mut self, token_refreshing: bool) -> Self {
+        self.config.token_refreshing = token_refreshing;
+        self
+    }
+
+    /// Sets [`Config::token_expiry_margin`], which can't be negative.
+    #[must_use]
+    pub fn token_expiry_margin(mut self, token_expiry_margin: chrono::Duration) -> Self {
+        self.config.token_expiry_margin = token_expiry_margin;
+        self
+    }
+
+    /// Sets [`Config::token_swap`].
+    #[must_use]
+    pub fn token_swap(mut self, token_swap: TokenSwap) -> Self {
+        self.config.token_swap = Some(token_swap);
+        self
+    }
+
+    /// Sets [`Config::cache_key`].
+    #[cfg(feature = "encrypted-cache")]
+    #[must_use]
+    pub fn cache_key(mut self, cache_key: model::CacheKey) -> Self {
+        self.config.cache_key = Some(cache_key);
+        self
+    }
+
+    /// Sets [`Config::timeout`], which can't be zero.
+    #[must_use]
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.config.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets [`Config::retry_policy`]. Its base delay can't be longer than its
+    /// maximum delay.
+    #[must_use]
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.config.retry_policy = retry_policy;
+        self
+    }
+
+    /// Validates the configuration and returns it.
+    pub fn build(self) -> Result<Config, ConfigError> {
+        let mut config = self.config;
+
+        match url::Url::parse(&config.prefix) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => {}
+            _ => return Err(ConfigError::InvalidPrefix(config.prefix)),
+        }
+        if !config.prefix.ends_with('/') {
+            config.prefix.push('/');
+        }
+
+        if !(1..=MAX_PAGINATION_CHUNKS).contains(&config.pagination_chunks) {
+            return Err(ConfigError::InvalidPaginationChunks(
+                config.pagination_chunks,
+            ));
+        }
+
+        if config.pagination_concurrency == 0 {
+            return Err(ConfigError::ZeroPaginationConcurrency);
+        }
+
+        if config.chunk_concurrency == 0 {
+            return Err(ConfigError::ZeroChunkConcurrency);
+        }
+
+        if config.token_expiry_margin < chrono::Duration::zero() {
+            return Err(ConfigError::InvalidTokenExpiryMargin(
+                config.token_expiry_margin,
+            ));
+        }
+
+        if config.timeout == Some(std::time::Duration::ZERO) {
+            return Err(ConfigError::ZeroTimeout);
+        }
+
+        let RetryPolicy {
+            base_delay,
+            max_delay,
+            ..
+        } = config.retry_policy;
+        if base_delay > max_delay {
+            return Err(ConfigError::InvalidRetryDelay {
+                base_delay,
+                max_delay,
+            });
+        }
+
+        Ok(config)
+    }
+}
+
+/// The URLs of a [token swap service][token-swap] you host yourself.
+///
+/// Applications distributed to end users, such as mobile or desktop ones,
+/// shouldn't include the client secret. With a token swap service, the
+/// authorization code and the refresh token are sent to your own server
+/// instead, which then talks to Spotify with the client secret and returns
+/// the resulting token.
+///
+/// The service receives a form with either the `code` or the `refresh_token`
+/// parameter, and it must respond with the same JSON body as Spotify's token
+/// endpoint.
+///
+/// [token-swap]: https://developer.spotify.com/documentation/ios/guides/token-swap-and-refresh/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenSwap {
+    /// The URL that exchanges an authorization code for a token.
+    pub swap_url: String,
+    /// The URL that exchanges a refresh token for a new access token.
+    pub refresh_url: String,
+}
+
+impl TokenSwap {
+    /// Initialization with both the swap and the refresh URLs
+    #[must_use]
+    pub fn new(swap_url: &str, refresh_url: &str) -> Self {
+        Self {
+            swap_url: swap_url.to_owned(),
+            refresh_url: refresh_url.to_owned(),
         }
     }
 }
@@ -292,6 +679,19 @@ pub(in crate) fn join_ids<'a, T: Id + 'a>(ids: impl IntoIterator<Item = T>) -> S
     ids.iter().map(Id::id).collect::<Vec<_>>().join(",")
 }
 
+/// Splits the IDs into comma-separated lists of at most `limit` IDs each, for
+/// the endpoints that only accept a certain number of them at once.
+pub(in crate) fn join_ids_chunked<'a, T: Id + 'a>(
+    ids: impl IntoIterator<Item = T>,
+    limit: usize,
+) -> Vec<String> {
+    let ids = ids
+        .into_iter()
+        .map(|id| id.id().to_owned())
+        .collect::<Vec<_>>();
+    ids.chunks(limit).map(|chunk| chunk.join(",")).collect()
+}
+
 #[inline]
 pub(in crate) fn join_scopes(scopes: &HashSet<String>) -> String {
     scopes
@@ -404,8 +804,11 @@ impl OAuth {
 
 #[cfg(test)]
 mod test {
-    use crate::{alphabets, generate_random_string, Credentials};
-    use std::collections::HashSet;
+    use crate::{
+        alphabets, generate_random_string, http::RetryPolicy, Config, ConfigError, Credentials,
+        MAX_PAGINATION_CHUNKS,
+    };
+    use std::{collections::HashSet, time::Duration};
 
     #[test]
     fn test_generate_random_string() {
@@ -431,4 +834,82 @@ mod test {
             Some(&"Basic cmFtc2F5OjEyMzQ1Ng==".to_owned())
         );
     }
+
+    #[test]
+    fn test_config_builder() {
+        let config = Config::builder()
+            .prefix("https://example.com/v1")
+            .pagination_chunks(MAX_PAGINATION_CHUNKS)
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        assert_eq!(config.prefix, "https://example.com/v1/");
+        assert_eq!(config.pagination_chunks, MAX_PAGINATION_CHUNKS);
+        assert_eq!(config.timeout, Some(Duration::from_secs(5)));
+
+        let config = Config::builder().build().unwrap();
+        assert_eq!(config.prefix, Config::default().prefix);
+    }
+
+    #[test]
+    fn test_config_builder_errors() {
+        let err = Config::builder().pagination_chunks(0).build().unwrap_err();
+        assert_eq!(err, ConfigError::InvalidPaginationChunks(0));
+        let err = Config::builder()
+            .pagination_chunks(MAX_PAGINATION_CHUNKS + 1)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ConfigError::InvalidPaginationChunks(MAX_PAGINATION_CHUNKS + 1)
+        );
+
+        for prefix in [
+            "",
+            "api.spotify.com/v1/",
+            "ftp://example.com/",
+            "file:///v1/",
+        ] {
+            let err = Config::builder().prefix(prefix).build().unwrap_err();
+            assert_eq!(err, ConfigError::InvalidPrefix(prefix.to_owned()));
+        }
+
+        let err = Config::builder()
+            .token_expiry_margin(chrono::Duration::seconds(-1))
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidTokenExpiryMargin(_)));
+
+        let err = Config::builder()
+            .timeout(Duration::ZERO)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, ConfigError::ZeroTimeout);
+
+        let err = Config::builder()
+            .pagination_concurrency(0)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, ConfigError::ZeroPaginationConcurrency);
+
+        let err = Config::builder().chunk_concurrency(0).build().unwrap_err();
+        assert_eq!(err, ConfigError::ZeroChunkConcurrency);
+
+        let retry_policy = RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_secs(10),
+            max_delay: Duration::from_secs(1),
+        };
+        let err = Config::builder()
+            .retry_policy(retry_policy)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ConfigError::InvalidRetryDelay {
+                base_delay: Duration::from_secs(10),
+                max_delay: Duration::from_secs(1),
+            }
+        );
+    }
 }
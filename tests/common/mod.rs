@@ -0,0 +1,155 @@
+//! A local stand-in for the Spotify API and the rest of services used by the
+//! clients, so that the tests don't depend on any external service and the
+//! requests made can be checked.
+
+// Each test crate only uses some of these helpers
+#![allow(dead_code)]
+
+use serde_json::Value;
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// A request received by the stand-in.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Request {
+    /// The request line, e.g. `GET /v1/me HTTP/1.1`.
+    pub line: String,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// The body of the request, which must be valid UTF-8.
+    pub fn text(&self) -> &str {
+        std::str::from_utf8(&self.body).unwrap()
+    }
+}
+
+/// A response of the stand-in. Its body may contain `{url}` to refer to the
+/// URL of the stand-in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    /// A successful response with a JSON body.
+    pub fn json(body: impl Into<String>) -> Self {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// A response with the given status code and an empty body.
+    pub fn status(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+        }
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+impl From<&str> for Response {
+    fn from(body: &str) -> Self {
+        Self::json(body)
+    }
+}
+
+impl From<Value> for Response {
+    fn from(body: Value) -> Self {
+        Self::json(body.to_string())
+    }
+}
+
+/// The requests received by the stand-in, in order.
+pub type Requests = Arc<Mutex<Vec<Request>>>;
+
+/// Starts a server that answers each request with the response returned by
+/// `respond`, until it returns `None`. It returns its URL, which ends with
+/// `path`, and the requests received.
+pub fn api_stand_in<F>(path: &str, mut respond: F) -> (String, Requests)
+where
+    F: FnMut(&Request) -> Option<Response> + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}{}", listener.local_addr().unwrap(), path);
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    let (server_url, received) = (url.clone(), Arc::clone(&requests));
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut reader = BufReader::new(stream.unwrap());
+
+            let mut request = Request::default();
+            reader.read_line(&mut request.line).unwrap();
+            request.line.truncate(request.line.trim_end().len());
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim_end().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    } else if name.eq_ignore_ascii_case("content-type") {
+                        request.content_type = Some(value.trim().to_owned());
+                    }
+                }
+            }
+            request.body = vec![0; content_length];
+            reader.read_exact(&mut request.body).unwrap();
+
+            let response = respond(&request);
+            received.lock().unwrap().push(request);
+            let response = match response {
+                Some(response) => response,
+                None => break,
+            };
+
+            let body = response.body.replace("{url}", &server_url);
+            let headers = response
+                .headers
+                .iter()
+                .map(|(name, value)| format!("{}: {}\r\n", name, value))
+                .collect::<String>();
+            let response = format!(
+                "HTTP/1.1 {} Status\r\n{}content-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                response.status,
+                headers,
+                body.len(),
+                body
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+        }
+    });
+
+    (url, requests)
+}
+
+/// Starts a server that answers each request with the next response in
+/// `responses`, and stops after the last one. It returns its URL, which ends
+/// with `path`, and the requests received.
+pub fn api_responses<R: Into<Response>>(path: &str, responses: Vec<R>) -> (String, Requests) {
+    let mut responses = responses
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<Response>>()
+        .into_iter();
+    api_stand_in(path, move |_| responses.next())
+}
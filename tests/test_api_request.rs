@@ -0,0 +1,126 @@
+//! The Spotify API is replaced by a local stand-in that answers with a fixed
+//! sequence of responses, so that the raw requests can be checked.
+
+use rspotify::{
+    clients::ApiRequest, model::Category, prelude::*, ClientCredsSpotify, Config, Credentials,
+    Token,
+};
+
+use serde_json::json;
+
+mod common;
+
+use common::api_responses;
+
+#[maybe_async::maybe_async]
+async fn client(prefix: &str) -> ClientCredsSpotify {
+    let config = Config::builder()
+        .prefix(prefix)
+        .pagination_chunks(1)
+        .build()
+        .unwrap();
+    let spotify = ClientCredsSpotify::with_config(Credentials::default(), config);
+    *spotify.token.lock().await.unwrap() = Some(Token {
+        access_token: "my-access-token".to_owned(),
+        ..Default::default()
+    });
+    spotify
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_api_request() {
+    let (url, requests) = api_responses(
+        "/v1/",
+        vec![
+            r#"{"href": "", "id": "pop", "icons": [], "name": "Pop"}"#,
+            "",
+        ],
+    );
+    let spotify = client(&url).await;
+
+    let request = ApiRequest::get("browse/categories/pop").query("locale", "es_ES");
+    let category: Category = spotify.api_request(&request).await.unwrap();
+    assert_eq!(category.name, "Pop");
+
+    let request = ApiRequest::put("me/following")
+        .query("type", "artist")
+        .body(json!({"ids": ["0OdUWJ0sBjDrqHygGUXeCF"]}));
+    let () = spotify.api_request(&request).await.unwrap();
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(
+        (requests[0].line.as_str(), requests[0].text()),
+        ("GET /v1/browse/categories/pop?locale=es_ES HTTP/1.1", "")
+    );
+    assert_eq!(
+        (requests[1].line.as_str(), requests[1].text()),
+        (
+            "PUT /v1/me/following?type=artist HTTP/1.1",
+            r#"{"ids":["0OdUWJ0sBjDrqHygGUXeCF"]}"#
+        )
+    );
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_api_request_paginated() {
+    let (url, requests) = api_responses(
+        "/v1/",
+        vec![
+            r#"{"categories": {"href": "", "items": [{"href": "", "id": "pop", "icons": [], "name": "Pop"}], "limit": 1, "next": "next", "offset": 0, "previous": null, "total": 2}}"#,
+            r#"{"categories": {"href": "", "items": [{"href": "", "id": "rock", "icons": [], "name": "Rock"}], "limit": 1, "next": null, "offset": 1, "previous": "previous", "total": 2}}"#,
+        ],
+    );
+    let spotify = client(&url).await;
+
+    let request = ApiRequest::get("browse/categories").query("locale", "es_ES");
+    let paginator = spotify.api_request_paginated::<Category>(request);
+
+    #[cfg(feature = "__async")]
+    let categories = {
+        use futures::stream::TryStreamExt;
+
+        paginator.try_collect::<Vec<_>>().await.unwrap()
+    };
+    #[cfg(feature = "__sync")]
+    let categories = paginator.collect::<Result<Vec<_>, _>>().unwrap();
+
+    let ids = categories.into_iter().map(|c| c.id).collect::<Vec<_>>();
+    assert_eq!(ids, ["pop", "rock"]);
+    let requests = requests.lock().unwrap();
+    assert_eq!(
+        requests[0].line,
+        "GET /v1/browse/categories?locale=es_ES&limit=1&offset=0 HTTP/1.1"
+    );
+    assert_eq!(
+        requests[1].line,
+        "GET /v1/browse/categories?locale=es_ES&limit=1&offset=1 HTTP/1.1"
+    );
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_api_request_paginated_wrapper() {
+    let (url, _) = api_responses(
+        "/v1/",
+        vec![
+            r#"{"albums": {"href": "{url}search?offset=0&limit=1&q=abba&type=album,playlist", "items": [{"href": "", "id": "album", "icons": [], "name": "Album"}], "limit": 1, "next": null, "offset": 0, "previous": null, "total": 1}, "playlists": {"href": "other", "items": [], "limit": 1, "next": null, "offset": 0, "previous": null, "total": 0}}"#,
+        ],
+    );
+    let spotify = client(&url).await;
+
+    let request = ApiRequest::get("search")
+        .query("q", "abba")
+        .query("type", "album,playlist");
+    let paginator = spotify.api_request_paginated::<Category>(request);
+
+    #[cfg(feature = "__async")]
+    let categories = {
+        use futures::stream::TryStreamExt;
+
+        paginator.try_collect::<Vec<_>>().await.unwrap()
+    };
+    #[cfg(feature = "__sync")]
+    let categories = paginator.collect::<Result<Vec<_>, _>>().unwrap();
+
+    let ids = categories.into_iter().map(|c| c.id).collect::<Vec<_>>();
+    assert_eq!(ids, ["album"]);
+}
@@ -0,0 +1,241 @@
+//! The bulk endpoints split their IDs into chunks that the API accepts. The
+//! Spotify API is replaced by a local stand-in that answers according to the
+//! IDs requested, so that the order of the merged results can be checked even
+//! when the chunks are requested concurrently.
+
+use rspotify::{
+    model::{ArtistId, EpisodeId, PlayableId, PlaylistId, TrackId},
+    prelude::*,
+    AuthCodeSpotify, Config, Credentials, Token,
+};
+
+use serde_json::{json, Value};
+
+mod common;
+
+use common::{api_stand_in, Response};
+
+/// Returns the IDs in the `ids` query parameter of a request line.
+fn requested_ids(request_line: &str) -> Vec<&str> {
+    let target = request_line.split(' ').nth(1).unwrap();
+    let query = target.split_once('?').unwrap().1;
+    let ids = query
+        .split('&')
+        .find_map(|param| param.strip_prefix("ids="))
+        .unwrap();
+    ids.split("%2C").flat_map(|ids| ids.split(',')).collect()
+}
+
+#[maybe_async::maybe_async]
+async fn client(prefix: &str) -> AuthCodeSpotify {
+    let config = Config::builder()
+        .prefix(prefix)
+        .chunk_concurrency(2)
+        .build()
+        .unwrap();
+    let spotify = AuthCodeSpotify::with_config(Credentials::default(), Default::default(), config);
+    *spotify.token.lock().await.unwrap() = Some(Token {
+        access_token: "my-access-token".to_owned(),
+        ..Default::default()
+    });
+    spotify
+}
+
+/// Generates `n` different valid IDs.
+fn ids(n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("{i:0>22}")).collect()
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_artists_in_chunks() {
+    let (url, requests) = api_stand_in("/v1/", |request| {
+        let artists = requested_ids(&request.line)
+            .into_iter()
+            .map(|id| {
+                json!({
+                    "external_urls": {},
+                    "followers": {"total": 0},
+                    "genres": [],
+                    "href": "",
+                    "id": id,
+                    "images": [],
+                    "name": id,
+                    "popularity": 0
+                })
+            })
+            .collect::<Vec<_>>();
+        Some(json!({ "artists": artists }).into())
+    });
+    let spotify = client(&url).await;
+
+    let ids = ids(120);
+    let artist_ids = ids.iter().map(|id| ArtistId::from_id(id).unwrap());
+    let artists = spotify.artists(artist_ids).await.unwrap();
+
+    let names = artists.into_iter().map(|a| a.name).collect::<Vec<_>>();
+    assert_eq!(names, ids);
+    let mut chunk_sizes = requests
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|request| requested_ids(&request.line).len())
+        .collect::<Vec<_>>();
+    chunk_sizes.sort_unstable();
+    assert_eq!(chunk_sizes, [20, 50, 50]);
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_saved_tracks_contains_in_chunks() {
+    // Only the tracks whose ID ends with an even number are saved
+    let (url, _) = api_stand_in("/v1/", |request| {
+        let contains = requested_ids(&request.line)
+            .into_iter()
+            .map(|id| id.as_bytes()[id.len() - 1] % 2 == 0)
+            .collect::<Vec<_>>();
+        Some(json!(contains).into())
+    });
+    let spotify = client(&url).await;
+
+    let ids = ids(60);
+    let track_ids = ids.iter().map(|id| TrackId::from_id(id).unwrap());
+    let contains = spotify
+        .current_user_saved_tracks_contains(track_ids)
+        .await
+        .unwrap();
+
+    let expected = (0..60).map(|i| i % 2 == 0).collect::<Vec<_>>();
+    assert_eq!(contains, expected);
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_playlist_add_items_in_chunks() {
+    let (url, requests) = api_stand_in("/v1/", |_| Some(json!({"snapshot_id": "snapshot"}).into()));
+    let spotify = client(&url).await;
+
+    let ids = ids(150);
+    let items = ids
+        .iter()
+        .map(|id| PlayableId::Track(TrackId::from_id(id).unwrap()));
+    let playlist_id = PlaylistId::from_id("3cEYpjA9oz9GiPac4AsH4n").unwrap();
+    let result = spotify
+        .playlist_add_items(playlist_id, items, Some(5))
+        .await
+        .unwrap();
+    assert_eq!(result.snapshot_id, "snapshot");
+
+    // The chunks are added one after the other, each after the previous one
+    let requests = requests.lock().unwrap();
+    let bodies = requests
+        .iter()
+        .map(|request| serde_json::from_str::<Value>(request.text()).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(bodies.len(), 2);
+    assert_eq!(bodies[0]["position"], 5);
+    assert_eq!(bodies[0]["uris"].as_array().unwrap().len(), 100);
+    assert_eq!(bodies[0]["uris"][0], format!("spotify:track:{}", ids[0]));
+    assert_eq!(bodies[1]["position"], 105);
+    assert_eq!(bodies[1]["uris"].as_array().unwrap().len(), 50);
+    assert_eq!(bodies[1]["uris"][0], format!("spotify:track:{}", ids[100]));
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_no_ids_no_requests() {
+    let (url, requests) = api_stand_in("/v1/", |_| Some(json!({}).into()));
+    let spotify = client(&url).await;
+
+    let artists = spotify.artists(Vec::<ArtistId>::new()).await.unwrap();
+    assert!(artists.is_empty());
+    let features = spotify
+        .tracks_features(Vec::<TrackId>::new())
+        .await
+        .unwrap();
+    assert!(features.is_empty());
+    assert!(requests.lock().unwrap().is_empty());
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_tracks_features_in_chunks() {
+    // The chunk with the first track has no features
+    let first = ids(1).remove(0);
+    let (url, _) = api_stand_in("/v1/", move |request| {
+        let ids = requested_ids(&request.line);
+        if ids.contains(&first.as_str()) {
+            return Some(Response::json(""));
+        }
+        let features = ids
+            .into_iter()
+            .map(|id| {
+                json!({
+                    "acousticness": 0.0,
+                    "analysis_url": "",
+                    "danceability": 0.0,
+                    "duration_ms": 0,
+                    "energy": 0.0,
+                    "id": id,
+                    "instrumentalness": 0.0,
+                    "key": 0,
+                    "liveness": 0.0,
+                    "loudness": 0.0,
+                    "mode": 1,
+                    "speechiness": 0.0,
+                    "tempo": 0.0,
+                    "time_signature": 4,
+                    "track_href": "",
+                    "valence": 0.0
+                })
+            })
+            .collect::<Vec<_>>();
+        Some(json!({ "audio_features": features }).into())
+    });
+    let spotify = client(&url).await;
+
+    let ids = ids(150);
+    let track_ids = ids.iter().map(|id| TrackId::from_id(id).unwrap());
+    let features = spotify.tracks_features(track_ids).await.unwrap();
+    assert_eq!(features.len(), ids.len());
+    assert!(features[..100].iter().all(Option::is_none));
+    let feature_ids = features[100..]
+        .iter()
+        .map(|f| f.as_ref().unwrap().id.id())
+        .collect::<Vec<_>>();
+    assert_eq!(feature_ids, ids[100..]);
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_saved_episodes_in_chunks() {
+    // Only the episodes whose ID ends with an even number are saved
+    let (url, requests) = api_stand_in("/v1/", |request| {
+        if !request.line.starts_with("GET") {
+            return Some(Response::json(""));
+        }
+        let contains = requested_ids(&request.line)
+            .into_iter()
+            .map(|id| id.as_bytes()[id.len() - 1] % 2 == 0)
+            .collect::<Vec<_>>();
+        Some(json!(contains).into())
+    });
+    let spotify = client(&url).await;
+
+    let ids = ids(60);
+    let episode_ids = || ids.iter().map(|id| EpisodeId::from_id(id).unwrap());
+    let contains = spotify
+        .current_user_saved_episodes_contains(episode_ids())
+        .await
+        .unwrap();
+    let expected = (0..60).map(|i| i % 2 == 0).collect::<Vec<_>>();
+    assert_eq!(contains, expected);
+
+    spotify
+        .current_user_saved_episodes_add(episode_ids())
+        .await
+        .unwrap();
+    let mut chunk_sizes = requests
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|request| request.line.starts_with("PUT"))
+        .map(|request| requested_ids(&request.line).len())
+        .collect::<Vec<_>>();
+    chunk_sizes.sort_unstable();
+    assert_eq!(chunk_sizes, [10, 50]);
+}
@@ -46,6 +46,7 @@ fn test_convert_search_type_from_str() {
 fn test_type_convert_from_str() {
     let artist = Type::Artist;
     assert_eq!(<&str>::from(artist), "artist");
+    assert_eq!("chapter".parse::<Type>(), Ok(Type::Chapter));
 }
 
 #[test]
@@ -64,6 +65,8 @@ fn test_current_playing_type() {
 fn test_search_type() {
     let episode = SearchType::Episode;
     assert_eq!(<&str>::from(episode), "episode");
+    let audiobook = SearchType::Audiobook;
+    assert_eq!(<&str>::from(audiobook), "audiobook");
 }
 
 #[test]
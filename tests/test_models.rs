@@ -265,6 +265,96 @@ fn test_full_episode() {
     assert_eq!(full_episode.release_date_precision, DatePrecision::Day);
     let duration = Duration::from_millis(1502795);
     assert_eq!(full_episode.duration, duration);
+
+    let json_str = format!(
+        r#"{{"added_at": "2022-05-01T12:34:56Z", "episode": {}}}"#,
+        json_str
+    );
+    let saved_episode: SavedEpisode = deserialize(json_str);
+    assert_eq!(saved_episode.added_at.timestamp(), 1651408496);
+    assert_eq!(saved_episode.episode, full_episode);
+}
+
+#[test]
+fn test_audiobook_and_chapter() {
+    let audiobook = r#"
+    {
+        "authors": [{"name": "Jane Austen"}],
+        "available_markets": ["US"],
+        "copyrights": [],
+        "description": "Pride and Prejudice",
+        "html_description": "<p>Pride and Prejudice</p>",
+        "edition": "Unabridged",
+        "explicit": false,
+        "external_urls": {
+            "spotify": "https://open.spotify.com/show/7iHfbu1YPACw6oZPAFJtqe"
+        },
+        "href": "https://api.spotify.com/v1/audiobooks/7iHfbu1YPACw6oZPAFJtqe",
+        "id": "7iHfbu1YPACw6oZPAFJtqe",
+        "images": [],
+        "languages": ["English"],
+        "media_type": "audio",
+        "name": "Pride and Prejudice",
+        "narrators": [{"name": "Rosamund Pike"}],
+        "publisher": "Penguin",
+        "type": "audiobook",
+        "uri": "spotify:show:7iHfbu1YPACw6oZPAFJtqe",
+        "total_chapters": 1
+    }
+    "#;
+    let chapter = r#"
+    {
+        "audio_preview_url": null,
+        "chapter_number": 0,
+        "description": "Chapter 1",
+        "html_description": "<p>Chapter 1</p>",
+        "duration_ms": 1502795,
+        "explicit": false,
+        "external_urls": {
+            "spotify": "https://open.spotify.com/episode/0D5wENdkdwbqlrHoaJ9g29"
+        },
+        "href": "https://api.spotify.com/v1/chapters/0D5wENdkdwbqlrHoaJ9g29",
+        "id": "0D5wENdkdwbqlrHoaJ9g29",
+        "images": [],
+        "is_playable": true,
+        "languages": ["en"],
+        "name": "Chapter 1",
+        "release_date": "2019-04-16",
+        "release_date_precision": "day",
+        "resume_point": {"fully_played": false, "resume_position_ms": 1000},
+        "type": "episode",
+        "uri": "spotify:episode:0D5wENdkdwbqlrHoaJ9g29"
+    }
+    "#;
+
+    let simplified: SimplifiedAudiobook = deserialize(audiobook);
+    assert_eq!(simplified.id.id(), "7iHfbu1YPACw6oZPAFJtqe");
+    assert_eq!(simplified.narrators[0].name, "Rosamund Pike");
+
+    let full = format!(
+        r#"{{"chapters": {{"href": "", "items": [{}], "limit": 50, "next": null, "offset": 0, "previous": null, "total": 1}}, {}"#,
+        chapter,
+        audiobook.trim().trim_start_matches('{')
+    );
+    let full: FullAudiobook = deserialize(full);
+    assert_eq!(
+        full.chapters.items[0].duration,
+        Duration::from_millis(1502795)
+    );
+    assert_eq!(full.chapters.items[0].audiobook, None);
+
+    let chapter = format!(
+        r#"{}, "audiobook": {}}}"#,
+        chapter.trim().trim_end_matches('}'),
+        audiobook
+    );
+    let chapter: Chapter = deserialize(&chapter);
+    assert_eq!(chapter.audiobook, Some(simplified));
+
+    // Chapters may be returned by the player as well
+    let item: PlayableItem = deserialize(serde_json::to_string(&chapter).unwrap());
+    assert_eq!(item, PlayableItem::Chapter(chapter));
+    assert!(item.id().is_none());
 }
 
 #[test]
@@ -356,6 +446,46 @@ fn test_recommendations_seed() {
     assert_eq!(seed._type, RecommendationsSeedType::Artist);
 }
 
+#[test]
+fn test_search_multiple_result() {
+    let json = r#"
+    {
+        "tracks": {
+            "href": "https://api.spotify.com/v1/search?query=abba&type=track&offset=0&limit=1",
+            "items": [],
+            "limit": 1,
+            "next": "https://api.spotify.com/v1/search?query=abba&type=track&offset=1&limit=1",
+            "offset": 0,
+            "previous": null,
+            "total": 800
+        },
+        "artists": {
+            "href": "https://api.spotify.com/v1/search?query=abba&type=artist&offset=0&limit=1",
+            "items": [],
+            "limit": 1,
+            "next": null,
+            "offset": 0,
+            "previous": null,
+            "total": 0
+        }
+    }
+    "#;
+    let result: SearchMultipleResult = deserialize(json);
+    assert_eq!(result.tracks.unwrap().total, 800);
+    assert_eq!(result.artists.unwrap().next, None);
+    assert!(result.albums.is_none());
+    assert!(result.playlists.is_none());
+}
+
+#[test]
+fn test_country_from_str() {
+    assert_eq!("ES".parse::<Country>(), Ok(Country::Spain));
+    assert_eq!("GB".parse::<Country>(), Ok(Country::UnitedKingdom));
+    assert_eq!("XK".parse::<Country>(), Ok(Country::Kosovo));
+    assert!("ZZ".parse::<Country>().is_err());
+    assert!("es".parse::<Country>().is_err());
+}
+
 #[test]
 fn test_full_playlist() {
     let json_str_images = r#"
@@ -741,6 +871,62 @@ fn test_currently_playing_context() {
     assert_eq!(currently_playing_context.progress, Some(duration));
 }
 
+#[test]
+fn test_current_user_queue() {
+    let track = r#"
+{
+  "album": {
+    "album_type": "single",
+    "artists": [],
+    "external_urls": {},
+    "href": "https://api.spotify.com/v1/albums/2lgOc40hhHqjUGAKMWqGxO",
+    "id": "2lgOc40hhHqjUGAKMWqGxO",
+    "images": [],
+    "name": "Playinwitme (feat. Kehlani)",
+    "release_date": "2018-03-20",
+    "release_date_precision": "day",
+    "type": "album",
+    "uri": "spotify:album:2lgOc40hhHqjUGAKMWqGxO"
+  },
+  "artists": [],
+  "available_markets": [],
+  "disc_number": 1,
+  "duration_ms": 191680,
+  "explicit": false,
+  "external_ids": {},
+  "external_urls": {},
+  "href": "https://api.spotify.com/v1/tracks/{id}",
+  "id": "{id}",
+  "is_local": false,
+  "name": "Playinwitme (feat. Kehlani)",
+  "popularity": 70,
+  "preview_url": null,
+  "track_number": 9,
+  "type": "track",
+  "uri": "spotify:track:{id}"
+}
+"#;
+    let first = "4F1yvJfQ7gJkrcgFJQDjOr";
+    let second = "43cFjTTCD9Cni4aSL0sORz";
+    let json = format!(
+        r#"{{"currently_playing": {}, "queue": [{}, {}]}}"#,
+        track.replace("{id}", first),
+        track.replace("{id}", second),
+        track.replace("{id}", first)
+    );
+    let queue: CurrentUserQueue = deserialize(json);
+    assert_eq!(queue.queue.len(), 2);
+
+    let first = PlayableId::Track(TrackId::from_id(first).unwrap());
+    let second = PlayableId::Track(TrackId::from_id(second).unwrap());
+    let missing = PlayableId::Track(TrackId::from_id("6rqhFgbbKwnb9MLmUQDhG6").unwrap());
+    assert!(queue.is_playing(&first));
+    assert!(!queue.is_playing(&second));
+    assert_eq!(queue.position(&second), Some(0));
+    assert_eq!(queue.position(&first), Some(1));
+    assert!(!queue.contains(&missing));
+}
+
 #[test]
 fn test_current_playback_context() {
     let json = r#"
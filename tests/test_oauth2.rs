@@ -1,7 +1,8 @@
 use chrono::prelude::*;
 use chrono::Duration;
 use rspotify::{
-    prelude::*, scopes, AuthCodeSpotify, ClientCredsSpotify, Config, Credentials, OAuth, Token,
+    prelude::*, scopes, AuthCodePkceSpotify, AuthCodeSpotify, ClientCredsSpotify, Config,
+    Credentials, OAuth, Token,
 };
 use std::{collections::HashMap, fs, io::Read, path::PathBuf};
 use url::Url;
@@ -41,6 +42,7 @@ async fn test_read_token_cache() {
     let tok = Token {
         expires_in,
         expires_at,
+        clock_skew: Duration::zero(),
         access_token: "test-access_token".to_owned(),
         scopes: scopes.clone(),
         refresh_token: Some("...".to_owned()),
@@ -70,6 +72,7 @@ async fn test_read_token_cache() {
 
     // delete cache file in the end
     fs::remove_file(&spotify.config.cache_path).unwrap();
+    fs::remove_file(".test_read_token_cache.json.lock").unwrap();
 }
 
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
@@ -81,6 +84,7 @@ async fn test_write_token() {
         access_token: "test-access_token".to_owned(),
         expires_in: Duration::seconds(3600),
         expires_at: Some(now),
+        clock_skew: Duration::zero(),
         scopes: scopes.clone(),
         refresh_token: Some("...".to_owned()),
     };
@@ -108,6 +112,175 @@ async fn test_write_token() {
 
     // delete cache file in the end
     fs::remove_file(&spotify.config.cache_path).unwrap();
+    fs::remove_file(".test_write_token_cache.json.lock").unwrap();
+}
+
+#[test]
+fn test_write_token_cache_concurrently() {
+    let path = PathBuf::from(".test_concurrent_token_cache.json");
+
+    // Every thread writes a different token many times, so that if the writes
+    // weren't atomic, the cache would end up corrupted at some point.
+    let handles = (0..8)
+        .map(|i| {
+            let path = path.clone();
+            std::thread::spawn(move || {
+                let tok = Token {
+                    access_token: format!("test-access_token-{}", i).repeat(100),
+                    ..Default::default()
+                };
+                for _ in 0..20 {
+                    tok.write_cache(&path).unwrap();
+                    let tok_from_file = Token::from_cache(&path).unwrap();
+                    assert!(tok_from_file.access_token.starts_with("test-access_token-"));
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // No temporary files are left behind, and only the owner can read it
+    assert!(!PathBuf::from(".test_concurrent_token_cache.json.tmp").exists());
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    // delete cache file in the end
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(".test_concurrent_token_cache.json.lock").unwrap();
+}
+
+#[test]
+fn test_read_missing_token_cache() {
+    let path = PathBuf::from(".test_missing_token_cache.json");
+    assert!(Token::from_cache(&path).is_err());
+
+    // A cache miss doesn't leave a lock file behind
+    assert!(!PathBuf::from(".test_missing_token_cache.json.lock").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_token_cache_stale_tmp() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = PathBuf::from(".test_stale_tmp_token_cache.json");
+    let tmp_path = PathBuf::from(".test_stale_tmp_token_cache.json.tmp");
+
+    // A temporary file left behind with wider permissions isn't reused
+    fs::write(&tmp_path, "stale").unwrap();
+    fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o644)).unwrap();
+    Token::default().write_cache(&path).unwrap();
+
+    let mode = fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+    assert!(!tmp_path.exists());
+
+    // delete cache file in the end
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(".test_stale_tmp_token_cache.json.lock").unwrap();
+}
+
+#[cfg(feature = "encrypted-cache")]
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_encrypted_token_cache() {
+    use rspotify::model::{CacheKey, CACHE_KEY_LENGTH};
+
+    let tok = Token {
+        access_token: "test-access_token".to_owned(),
+        expires_in: Duration::seconds(3600),
+        expires_at: Some(Utc::now() + Duration::seconds(3600)),
+        refresh_token: Some("test-refresh_token".to_owned()),
+        ..Default::default()
+    };
+
+    let config = Config {
+        token_cached: true,
+        cache_path: PathBuf::from(".test_encrypted_token_cache.json"),
+        cache_key: Some(CacheKey::Raw([42; CACHE_KEY_LENGTH])),
+        ..Default::default()
+    };
+
+    // Existing plaintext caches can still be read
+    tok.write_cache(&config.cache_path).unwrap();
+    let mut spotify = ClientCredsSpotify::from_token(tok.clone());
+    spotify.config = config.clone();
+    let tok_from_file = spotify.read_token_cache().await.unwrap().unwrap();
+    assert_eq!(tok_from_file, tok);
+
+    // The cache is written encrypted, which can't be read without the key
+    spotify.write_token_cache().await.unwrap();
+    let contents = fs::read_to_string(&config.cache_path).unwrap();
+    assert!(!contents.contains("test-access_token"));
+    assert!(!contents.contains("test-refresh_token"));
+    assert!(Token::from_cache(&config.cache_path).is_err());
+    let tok_from_file = spotify.read_token_cache().await.unwrap().unwrap();
+    assert_eq!(tok_from_file, tok);
+
+    // Nor with the wrong key
+    spotify.config.cache_key = Some(CacheKey::Raw([0; CACHE_KEY_LENGTH]));
+    let result = spotify.read_token_cache().await;
+    assert!(result.is_err());
+
+    // delete cache file in the end
+    fs::remove_file(&config.cache_path).unwrap();
+    fs::remove_file(".test_encrypted_token_cache.json.lock").unwrap();
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_logout() {
+    let config = Config {
+        token_cached: true,
+        cache_path: PathBuf::from(".test_logout_token_cache.json"),
+        ..Default::default()
+    };
+    let oauth = OAuth {
+        redirect_uri: "localhost".to_owned(),
+        ..Default::default()
+    };
+    let mut spotify = AuthCodeSpotify::with_config(Credentials::default(), oauth, config);
+    *spotify.token.lock().await.unwrap() = Some(Token::default());
+    spotify.write_token_cache().await.unwrap();
+    assert!(spotify.config.cache_path.exists());
+
+    let old_state = spotify.oauth.state.clone();
+    spotify.logout().await.unwrap();
+
+    let token = spotify.token.lock().await.unwrap().clone();
+    assert!(token.is_none());
+    assert!(!spotify.config.cache_path.exists());
+    assert_ne!(spotify.oauth.state, old_state);
+
+    // Logging out again is fine, even without a cache
+    spotify.logout().await.unwrap();
+
+    // delete lock file in the end
+    fs::remove_file(".test_logout_token_cache.json.lock").unwrap();
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_logout_pkce() {
+    let oauth = OAuth {
+        redirect_uri: "localhost".to_owned(),
+        ..Default::default()
+    };
+    let mut spotify = AuthCodePkceSpotify::new(Credentials::new_pkce("client-id"), oauth);
+    spotify.get_authorize_url(None).unwrap();
+    *spotify.token.lock().await.unwrap() = Some(Token::default());
+    assert!(spotify.verifier.is_some());
+
+    let old_state = spotify.oauth.state.clone();
+    spotify.logout().await.unwrap();
+
+    let token = spotify.token.lock().await.unwrap().clone();
+    assert!(token.is_none());
+    assert!(spotify.verifier.is_none());
+    assert_ne!(spotify.oauth.state, old_state);
 }
 
 #[test]
@@ -118,6 +291,7 @@ fn test_token_is_expired() {
         access_token: "test-access_token".to_owned(),
         expires_in,
         expires_at: Some(Utc::now() + expires_in),
+        clock_skew: Duration::zero(),
         refresh_token: Some("...".to_owned()),
     };
     assert!(!tok.is_expired());
@@ -128,6 +302,7 @@ fn test_token_is_expired() {
         access_token: "test-access_token".to_owned(),
         expires_in,
         expires_at: Some(Utc::now() + expires_in),
+        clock_skew: Duration::zero(),
         refresh_token: Some("...".to_owned()),
     };
     assert!(tok.is_expired());
@@ -0,0 +1,67 @@
+//! The Spotify API is replaced by a local stand-in that answers with a page
+//! nested inside a wrapper object, like `search` does, so that its links can
+//! be followed.
+
+use rspotify::{
+    model::{FullTrack, Page},
+    prelude::*,
+    ClientCredsSpotify, Token,
+};
+
+mod common;
+
+use common::api_responses;
+
+fn client() -> ClientCredsSpotify {
+    ClientCredsSpotify::from_token(Token {
+        access_token: "my-access-token".to_owned(),
+        ..Default::default()
+    })
+}
+
+fn page(href: String, next: Option<String>, previous: Option<String>) -> Page<FullTrack> {
+    Page {
+        href,
+        items: Vec::new(),
+        limit: 1,
+        next,
+        offset: 0,
+        previous,
+        total: 2,
+    }
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_next_page_in_wrapper() {
+    let (url, requests) = api_responses(
+        "/v1",
+        vec![
+            r#"{"tracks": {"href": "{url}/search?q=a&type=track&offset=1&limit=1", "items": [], "limit": 1, "next": null, "offset": 1, "previous": "{url}/search?q=a&type=track&offset=0&limit=1", "total": 2}}"#,
+        ],
+    );
+    let first = page(
+        format!("{}/search?q=a&type=track&offset=0&limit=1", url),
+        Some(format!("{}/search?q=a&type=track&offset=1&limit=1", url)),
+        None,
+    );
+
+    let second = client().next_page(&first).await.unwrap().unwrap();
+    assert_eq!(second.offset, 1);
+    assert_eq!(second.next, None);
+    assert_eq!(second.previous.as_ref(), Some(&first.href));
+    assert_eq!(
+        requests.lock().unwrap()[0].line,
+        "GET /v1/search?q=a&type=track&offset=1&limit=1 HTTP/1.1"
+    );
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_no_page_links() {
+    let first = page("https://api.spotify.com/v1/search".to_owned(), None, None);
+    let spotify = client();
+
+    let next = spotify.next_page(&first).await.unwrap();
+    assert!(next.is_none());
+    let previous = spotify.previous_page(&first).await.unwrap();
+    assert!(previous.is_none());
+}
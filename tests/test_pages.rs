@@ -0,0 +1,89 @@
+//! The Spotify API is replaced by a local stand-in that answers with a fixed
+//! sequence of pages, so that they can be followed with their progress.
+
+use rspotify::{
+    clients::pagination::Progress, prelude::*, ClientCredsSpotify, Config, Credentials, Token,
+};
+
+mod common;
+
+use common::api_responses;
+
+#[maybe_async::maybe_async]
+async fn client(prefix: &str) -> ClientCredsSpotify {
+    let config = Config::builder()
+        .prefix(prefix)
+        .pagination_chunks(1)
+        .build()
+        .unwrap();
+    let spotify = ClientCredsSpotify::with_config(Credentials::default(), config);
+    *spotify.token.lock().await.unwrap() = Some(Token {
+        access_token: "my-access-token".to_owned(),
+        ..Default::default()
+    });
+    spotify
+}
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_pages_with_progress() {
+    let (url, requests) = api_responses(
+        "/v1/",
+        vec![
+            r#"{"categories": {"href": "", "items": [{"href": "", "id": "pop", "icons": [], "name": "Pop"}], "limit": 1, "next": "next", "offset": 0, "previous": null, "total": 2}}"#,
+            r#"{"categories": {"href": "", "items": [{"href": "", "id": "rock", "icons": [], "name": "Rock"}], "limit": 1, "next": null, "offset": 1, "previous": "previous", "total": 2}}"#,
+        ],
+    );
+    let spotify = client(&url).await;
+
+    let mut pages = spotify.pages(|limit, offset| {
+        spotify.categories_manual(Some("es_ES"), None, Some(limit), Some(offset))
+    });
+    let mut seen = Vec::new();
+    loop {
+        #[cfg(feature = "__async")]
+        let page = {
+            use futures::stream::TryStreamExt;
+
+            pages.try_next().await.unwrap()
+        };
+        #[cfg(feature = "__sync")]
+        let page = pages.next().transpose().unwrap();
+
+        let page = match page {
+            Some(page) => page,
+            None => break,
+        };
+        let ids = page.items.into_iter().map(|c| c.id).collect::<Vec<_>>();
+        seen.push((ids, pages.progress()));
+    }
+
+    assert_eq!(
+        seen,
+        [
+            (
+                vec!["pop".to_owned()],
+                Progress {
+                    fetched: 1,
+                    total: Some(2)
+                }
+            ),
+            (
+                vec!["rock".to_owned()],
+                Progress {
+                    fetched: 2,
+                    total: Some(2)
+                }
+            ),
+        ]
+    );
+    let requests = requests.lock().unwrap();
+    let offsets = requests
+        .iter()
+        .map(|request| {
+            request
+                .line
+                .split(&['?', '&', ' '][..])
+                .find(|p| p.starts_with("offset="))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(offsets, [Some("offset=0"), Some("offset=1")]);
+}
@@ -0,0 +1,107 @@
+//! The owned paginators must be `Send + 'static`, so that they can be moved to
+//! other tasks or threads. The Spotify API is replaced by a local stand-in
+//! that answers every request with an empty page.
+
+use rspotify::{
+    model::{
+        AlbumId, ArtistId, AudiobookId, FullTrack, Market, PlaylistId, ShowId, TimeRange, UserId,
+    },
+    prelude::*,
+    AuthCodeSpotify, ClientCredsSpotify, Config, Credentials, Token,
+};
+
+mod common;
+
+use common::api_stand_in;
+
+const EMPTY_PAGE: &str =
+    r#"{"href":"","items":[],"limit":50,"next":null,"offset":0,"previous":null,"total":0}"#;
+
+/// Starts a server that answers each request with an empty page, returning
+/// its URL.
+fn empty_pages() -> String {
+    api_stand_in("/v1", |_| Some(EMPTY_PAGE.into())).0
+}
+
+#[maybe_async::maybe_async]
+async fn client(prefix: &str) -> ClientCredsSpotify {
+    let config = Config::builder().prefix(prefix).build().unwrap();
+    let spotify = ClientCredsSpotify::with_config(Credentials::default(), config);
+    *spotify.token.lock().await.unwrap() = Some(Token {
+        access_token: "my-access-token".to_owned(),
+        ..Default::default()
+    });
+    spotify
+}
+
+fn artist() -> ArtistId<'static> {
+    ArtistId::from_id("0OdUWJ0sBjDrqHygGUXeCF").unwrap()
+}
+
+fn require_send<T: Send + 'static>(_: &T) {}
+
+#[test]
+fn test_owned_paginators_are_send() {
+    let spotify = ClientCredsSpotify::default();
+    let market = Some(Market::FromToken);
+    require_send(&spotify.artist_albums_owned(artist(), None, market));
+    require_send(&spotify.album_track_owned(AlbumId::from_id("6akEvsycLGftJxYudPjmqK").unwrap()));
+    require_send(
+        &spotify
+            .get_shows_episodes_owned(ShowId::from_id("6NPGaAKfLUZbBjHzHJgvd1").unwrap(), market),
+    );
+    require_send(&spotify.categories_owned(Some("es_ES".to_owned()), market));
+    require_send(&spotify.category_playlists_owned("pop".to_owned(), market));
+    require_send(&spotify.new_releases_owned(market));
+    require_send(&spotify.playlist_items_owned(
+        PlaylistId::from_id("37i9dQZF1DXcBWIGoYBM5M").unwrap(),
+        None,
+        market,
+    ));
+    require_send(&spotify.user_playlists_owned(UserId::from_id("spotify").unwrap()));
+    require_send(&spotify.get_audiobook_chapters_owned(
+        AudiobookId::from_id("7iHfbu1YPACw6oZPAFJtqe").unwrap(),
+        market,
+    ));
+    require_send(&spotify.search_items_owned::<FullTrack>("abba", market, None));
+
+    let spotify = AuthCodeSpotify::default();
+    require_send(&spotify.current_user_playlists_owned());
+    require_send(&spotify.current_user_saved_albums_owned(market));
+    require_send(&spotify.current_user_saved_tracks_owned(market));
+    require_send(&spotify.current_user_saved_episodes_owned(market));
+    require_send(&spotify.current_user_followed_artists_owned());
+    require_send(&spotify.current_user_top_artists_owned(Some(TimeRange::ShortTerm)));
+    require_send(&spotify.current_user_top_tracks_owned(None));
+    require_send(&spotify.current_user_recently_played_owned(None));
+    require_send(&spotify.get_saved_show_owned());
+    require_send(&spotify.current_user_saved_audiobooks_owned());
+}
+
+#[cfg(feature = "__async")]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_spawn_owned_paginator() {
+    use futures::stream::TryStreamExt;
+
+    let spotify = client(&empty_pages()).await;
+    let albums = spotify.artist_albums_owned(artist(), None, None);
+    let tracks = spotify.album_track_owned(AlbumId::from_id("6akEvsycLGftJxYudPjmqK").unwrap());
+
+    let albums = tokio::spawn(albums.try_collect::<Vec<_>>());
+    let tracks = tokio::spawn(tracks.try_collect::<Vec<_>>());
+    assert!(albums.await.unwrap().unwrap().is_empty());
+    assert!(tracks.await.unwrap().unwrap().is_empty());
+}
+
+#[cfg(feature = "__sync")]
+#[test]
+fn test_spawn_owned_paginator() {
+    let spotify = client(&empty_pages());
+    let albums = spotify.artist_albums_owned(artist(), None, None);
+    let tracks = spotify.album_track_owned(AlbumId::from_id("6akEvsycLGftJxYudPjmqK").unwrap());
+
+    let albums = std::thread::spawn(move || albums.collect::<Result<Vec<_>, _>>());
+    let tracks = std::thread::spawn(move || tracks.collect::<Result<Vec<_>, _>>());
+    assert!(albums.join().unwrap().unwrap().is_empty());
+    assert!(tracks.join().unwrap().unwrap().is_empty());
+}
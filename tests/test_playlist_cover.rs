@@ -0,0 +1,95 @@
+//! The Spotify API is replaced by a local stand-in, so that the requests of
+//! the playlist cover image endpoints can be checked.
+
+use rspotify::{
+    model::PlaylistId, prelude::*, AuthCodeSpotify, ClientError, Config, Credentials, Token,
+};
+
+mod common;
+
+use common::{api_responses, Request};
+
+#[maybe_async::maybe_async]
+async fn client(prefix: &str) -> AuthCodeSpotify {
+    let config = Config::builder().prefix(prefix).build().unwrap();
+    let spotify = AuthCodeSpotify::with_config(Credentials::default(), Default::default(), config);
+    *spotify.token.lock().await.unwrap() = Some(Token {
+        access_token: "my-access-token".to_owned(),
+        ..Default::default()
+    });
+    spotify
+}
+
+fn playlist() -> PlaylistId<'static> {
+    PlaylistId::from_id("3cEYpjA9oz9GiPac4AsH4n").unwrap()
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_playlist_cover_image() {
+    let (url, requests) = api_responses(
+        "/v1/",
+        vec![
+            r#"[{"url": "https://i.scdn.co/image/ab67616d00001e02", "height": 300, "width": 300}]"#,
+        ],
+    );
+    let spotify = client(&url).await;
+
+    let images = spotify.playlist_cover_image(playlist()).await.unwrap();
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].height, Some(300));
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(
+        requests[0].line,
+        "GET /v1/playlists/3cEYpjA9oz9GiPac4AsH4n/images HTTP/1.1"
+    );
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_playlist_upload_cover_image() {
+    let (url, requests) = api_responses("/v1/", vec![""]);
+    let spotify = client(&url).await;
+
+    let image = [0xff, 0xd8, 0xff, 0xe0, 0x00, 0x10];
+    spotify
+        .playlist_upload_cover_image(playlist(), &image)
+        .await
+        .unwrap();
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(
+        requests[0],
+        Request {
+            line: "PUT /v1/playlists/3cEYpjA9oz9GiPac4AsH4n/images HTTP/1.1".to_owned(),
+            content_type: Some("image/jpeg".to_owned()),
+            body: b"/9j/4AAQ".to_vec(),
+        }
+    );
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_playlist_upload_cover_image_too_large() {
+    let (url, requests) = api_responses("/v1/", vec![""]);
+    let spotify = client(&url).await;
+
+    // 192 KB are exactly 256 KB once encoded in base64
+    let image = vec![0; 192 * 1024];
+    spotify
+        .playlist_upload_cover_image(playlist(), &image)
+        .await
+        .unwrap();
+
+    let image = vec![0; 192 * 1024 + 1];
+    let result = spotify
+        .playlist_upload_cover_image(playlist(), &image)
+        .await;
+    assert!(matches!(
+        result,
+        Err(ClientError::ImageTooLarge(len)) if len == 256 * 1024 + 4
+    ));
+
+    // The second image is rejected before making the request
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].body.len(), 256 * 1024);
+}
@@ -0,0 +1,107 @@
+//! The Spotify API is replaced by a local stand-in that answers with a fixed
+//! sequence of responses, so that the retries can be checked.
+
+use rspotify::{
+    http::RetryPolicy,
+    model::{ArtistId, TrackId},
+    prelude::*,
+    AuthCodeSpotify, ClientError, Config, Credentials, Token,
+};
+
+use std::time::Duration;
+
+mod common;
+
+use common::{api_responses, Response};
+
+#[maybe_async::maybe_async]
+async fn client(prefix: &str, max_retries: u32) -> AuthCodeSpotify {
+    let config = Config::builder()
+        .prefix(prefix)
+        .timeout(Duration::from_secs(10))
+        .retry_policy(RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(100),
+        })
+        .build()
+        .unwrap();
+    let spotify = AuthCodeSpotify::with_config(Credentials::default(), Default::default(), config);
+    *spotify.token.lock().await.unwrap() = Some(Token {
+        access_token: "my-access-token".to_owned(),
+        ..Default::default()
+    });
+    spotify
+}
+
+fn artist() -> ArtistId<'static> {
+    ArtistId::from_id("0OdUWJ0sBjDrqHygGUXeCF").unwrap()
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_retry_server_errors() {
+    let (url, requests) = api_responses(
+        "/v1",
+        vec![
+            Response::status(503),
+            Response::status(429).header("retry-after", "0"),
+            Response::json(r#"{"artists": []}"#),
+        ],
+    );
+    let spotify = client(&url, 2).await;
+
+    let artists = spotify.artist_related_artists(artist()).await.unwrap();
+    assert!(artists.is_empty());
+    assert_eq!(requests.lock().unwrap().len(), 3);
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_retry_gives_up() {
+    let (url, requests) = api_responses("/v1", vec![Response::status(500), Response::status(500)]);
+    let spotify = client(&url, 1).await;
+
+    let result = spotify.artist_related_artists(artist()).await;
+    assert!(matches!(result, Err(ClientError::Http(_))));
+    assert_eq!(requests.lock().unwrap().len(), 2);
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_no_retry_on_client_errors() {
+    let (url, requests) = api_responses("/v1", vec![Response::status(404)]);
+    let spotify = client(&url, 3).await;
+
+    let result = spotify.artist_related_artists(artist()).await;
+    assert!(matches!(result, Err(ClientError::Http(_))));
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_no_retry_on_server_errors_for_writes() {
+    let (url, requests) = api_responses("/v1", vec![Response::status(503)]);
+    let spotify = client(&url, 3).await;
+
+    let track = TrackId::from_id("4iV5W9uYEdYUVa79Axb7Rh").unwrap();
+    let result = spotify.current_user_saved_tracks_add([track]).await;
+    assert!(matches!(result, Err(ClientError::Http(_))));
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_retry_rate_limited_writes() {
+    let (url, requests) = api_responses(
+        "/v1",
+        vec![
+            Response::status(429),
+            Response::status(429).header("retry-after", "0"),
+            Response::json(r#"{"artists": []}"#),
+        ],
+    );
+    let spotify = client(&url, 2).await;
+
+    let track = TrackId::from_id("4iV5W9uYEdYUVa79Axb7Rh").unwrap();
+    spotify
+        .current_user_saved_tracks_add([track])
+        .await
+        .unwrap();
+    assert_eq!(requests.lock().unwrap().len(), 3);
+}
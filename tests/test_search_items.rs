@@ -0,0 +1,150 @@
+//! The Spotify API is replaced by a local stand-in that answers with the
+//! pages of a search, so that the type of the results is inferred.
+
+use rspotify::{
+    model::{Checkpoint, FullArtist, SearchQuery},
+    prelude::*,
+    ClientCredsSpotify, Config, Credentials, Token,
+};
+
+mod common;
+
+use common::api_responses;
+
+/// Splits a request line into its path and its sorted query parameters, as
+/// their order isn't fixed.
+fn parse_request_line(line: &str) -> (&str, Vec<&str>) {
+    let target = line.split(' ').nth(1).unwrap();
+    let (path, query) = target.split_once('?').unwrap();
+    let mut params = query.split('&').collect::<Vec<_>>();
+    params.sort_unstable();
+    (path, params)
+}
+
+#[maybe_async::maybe_async]
+async fn client(prefix: &str) -> ClientCredsSpotify {
+    let config = Config::builder()
+        .prefix(prefix)
+        .pagination_chunks(1)
+        .build()
+        .unwrap();
+    let spotify = ClientCredsSpotify::with_config(Credentials::default(), config);
+    *spotify.token.lock().await.unwrap() = Some(Token {
+        access_token: "my-access-token".to_owned(),
+        ..Default::default()
+    });
+    spotify
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_search_items_manual() {
+    let (url, requests) = api_responses(
+        "/v1/",
+        vec![
+            r#"{"artists": {"href": "", "items": [], "limit": 10, "next": null, "offset": 0, "previous": null, "total": 0}}"#,
+        ],
+    );
+    let spotify = client(&url).await;
+
+    let query = SearchQuery::new().artist("abba");
+    let page = spotify
+        .search_items_manual::<FullArtist>(query, None, None, Some(10), None)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 0);
+    assert_eq!(
+        parse_request_line(&requests.lock().unwrap()[0].line),
+        (
+            "/v1/search",
+            vec!["limit=10", "q=artist%3Aabba", "type=artist"]
+        )
+    );
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_search_items() {
+    let (url, requests) = api_responses(
+        "/v1/",
+        vec![
+            r#"{"artists": {"href": "", "items": [{"external_urls": {}, "followers": {"total": 0}, "genres": [], "href": "", "id": "0LcJLqbBmaGUft1e9Mm8HV", "images": [], "name": "ABBA", "popularity": 80}], "limit": 1, "next": "next", "offset": 0, "previous": null, "total": 2}}"#,
+            r#"{"artists": {"href": "", "items": [{"external_urls": {}, "followers": {"total": 0}, "genres": [], "href": "", "id": "3vxotOnOGDlZXyzJPLFnm2", "images": [], "name": "ABBA Teens", "popularity": 20}], "limit": 1, "next": null, "offset": 1, "previous": "previous", "total": 2}}"#,
+        ],
+    );
+    let spotify = client(&url).await;
+
+    let paginator = spotify.search_items::<FullArtist>("abba", None, None);
+
+    #[cfg(feature = "__async")]
+    let artists = {
+        use futures::stream::TryStreamExt;
+
+        paginator.try_collect::<Vec<_>>().await.unwrap()
+    };
+    #[cfg(feature = "__sync")]
+    let artists = paginator.collect::<Result<Vec<_>, _>>().unwrap();
+
+    let names = artists.into_iter().map(|a| a.name).collect::<Vec<_>>();
+    assert_eq!(names, ["ABBA", "ABBA Teens"]);
+    let requests = requests.lock().unwrap();
+    assert_eq!(
+        parse_request_line(&requests[0].line),
+        (
+            "/v1/search",
+            vec!["limit=1", "offset=0", "q=abba", "type=artist"]
+        )
+    );
+    assert_eq!(
+        parse_request_line(&requests[1].line),
+        (
+            "/v1/search",
+            vec!["limit=1", "offset=1", "q=abba", "type=artist"]
+        )
+    );
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_search_items_window() {
+    let (url, requests) = api_responses(
+        "/v1/",
+        vec![
+            r#"{"artists": {"href": "", "items": [{"external_urls": {}, "followers": {"total": 0}, "genres": [], "href": "", "id": "0LcJLqbBmaGUft1e9Mm8HV", "images": [], "name": "ABBA", "popularity": 80}], "limit": 1, "next": "next", "offset": 999, "previous": "previous", "total": 5000}}"#,
+        ],
+    );
+    let config = Config::builder()
+        .prefix(&url)
+        .pagination_chunks(50)
+        .build()
+        .unwrap();
+    let spotify = ClientCredsSpotify::with_config(Credentials::default(), config);
+    *spotify.token.lock().await.unwrap() = Some(Token {
+        access_token: "my-access-token".to_owned(),
+        ..Default::default()
+    });
+
+    // The results past the offset 1000 aren't requested, as Spotify doesn't
+    // serve them
+    let paginator = spotify
+        .search_items::<FullArtist>("abba", None, None)
+        .resume(Checkpoint::Offset(999))
+        .unwrap();
+
+    #[cfg(feature = "__async")]
+    let artists = {
+        use futures::stream::TryStreamExt;
+
+        paginator.try_collect::<Vec<_>>().await.unwrap()
+    };
+    #[cfg(feature = "__sync")]
+    let artists = paginator.collect::<Result<Vec<_>, _>>().unwrap();
+
+    assert_eq!(artists.len(), 1);
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        parse_request_line(&requests[0].line),
+        (
+            "/v1/search",
+            vec!["limit=1", "offset=999", "q=abba", "type=artist"]
+        )
+    );
+}
@@ -0,0 +1,85 @@
+//! The token swap service is replaced by a local stand-in that answers a
+//! single request, so that these tests don't depend on any external service.
+
+use rspotify::{prelude::*, scopes, AuthCodeSpotify, Config, Credentials, OAuth, Token, TokenSwap};
+
+use std::collections::HashMap;
+
+use chrono::{prelude::*, Duration};
+use url::form_urlencoded;
+
+mod common;
+
+use common::{api_responses, Requests};
+
+const TOKEN_RESPONSE: &str = r#"{
+    "access_token": "swapped-access-token",
+    "token_type": "Bearer",
+    "scope": "user-read-private",
+    "expires_in": 3600,
+    "refresh_token": "swapped-refresh-token"
+}"#;
+
+/// Returns the form sent in the only request received.
+fn sent_form(requests: &Requests) -> HashMap<String, String> {
+    let requests = requests.lock().unwrap();
+    form_urlencoded::parse(&requests[0].body)
+        .into_owned()
+        .collect()
+}
+
+/// The credentials don't include the client secret, since it's not needed.
+fn swap_client(swap: TokenSwap) -> AuthCodeSpotify {
+    let config = Config {
+        token_swap: Some(swap),
+        ..Default::default()
+    };
+    let oauth = OAuth {
+        redirect_uri: "http://localhost:8888/callback".to_owned(),
+        scopes: scopes!("user-read-private"),
+        ..Default::default()
+    };
+
+    AuthCodeSpotify::with_config(Credentials::new_pkce("my-client-id"), oauth, config)
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_token_swap_request_token() {
+    let (url, requests) = api_responses("/swap", vec![TOKEN_RESPONSE]);
+    let spotify = swap_client(TokenSwap::new(&url, "http://127.0.0.1:1/unused"));
+
+    spotify.request_token("my-auth-code").await.unwrap();
+
+    // Only the code is sent to the service
+    let form = sent_form(&requests);
+    assert_eq!(form.len(), 1);
+    assert_eq!(form.get("code").unwrap(), "my-auth-code");
+
+    let token = spotify.token.lock().await.unwrap().clone().unwrap();
+    assert_eq!(token.access_token, "swapped-access-token");
+    assert_eq!(token.refresh_token.unwrap(), "swapped-refresh-token");
+    assert_eq!(token.scopes, scopes!("user-read-private"));
+    assert_eq!(token.expires_in, Duration::seconds(3600));
+    assert!(token.expires_at.unwrap() > Utc::now());
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_token_swap_refresh_token() {
+    let (url, requests) = api_responses("/swap", vec![TOKEN_RESPONSE]);
+    let spotify = swap_client(TokenSwap::new("http://127.0.0.1:1/unused", &url));
+    *spotify.token.lock().await.unwrap() = Some(Token {
+        refresh_token: Some("my-refresh-token".to_owned()),
+        ..Default::default()
+    });
+
+    spotify.refresh_token().await.unwrap();
+
+    // Only the refresh token is sent to the service
+    let form = sent_form(&requests);
+    assert_eq!(form.len(), 1);
+    assert_eq!(form.get("refresh_token").unwrap(), "my-refresh-token");
+
+    let token = spotify.token.lock().await.unwrap().clone().unwrap();
+    assert_eq!(token.access_token, "swapped-access-token");
+    assert_eq!(token.refresh_token.unwrap(), "my-refresh-token");
+}
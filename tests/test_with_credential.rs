@@ -164,6 +164,28 @@ async fn test_fake_playlist() {
     assert!(playlist.is_err());
 }
 
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_category() {
+    let category = creds_client()
+        .await
+        .category("toplists", Some(Market::Country(Country::Spain)), None)
+        .await
+        .unwrap();
+    assert_eq!(category.id, "toplists");
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_available_genre_seeds() {
+    let genres = creds_client().await.available_genre_seeds().await.unwrap();
+    assert!(genres.iter().any(|genre| genre == "rock"));
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_available_markets() {
+    let markets = creds_client().await.available_markets().await.unwrap();
+    assert!(markets.contains(&Country::Spain));
+}
+
 mod test_pagination {
     use super::*;
 
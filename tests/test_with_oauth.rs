@@ -18,9 +18,10 @@
 use rspotify::{
     clients::pagination::Paginator,
     model::{
-        AlbumId, ArtistId, Country, CurrentPlaybackContext, Device, EpisodeId, FullPlaylist,
-        ItemPositions, Market, Offset, PlaylistId, RecommendationsAttribute, RepeatState,
-        SearchType, ShowId, TimeLimits, TimeRange, TrackId, UserId,
+        AlbumId, ArtistId, AudiobookId, Country, CurrentPlaybackContext, Device, EpisodeId,
+        FullPlaylist, ItemPositions, Market, Offset, PlaylistId, RecommendationsAttribute,
+        RepeatState, SearchFilter, SearchQuery, SearchType, ShowId, TimeLimits, TimeRange, TrackId,
+        UserId,
     },
     prelude::*,
     scopes, AuthCodeSpotify, ClientResult, Credentials, OAuth, Token,
@@ -163,11 +164,12 @@ async fn test_current_playing() {
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
 #[ignore]
 async fn test_current_user_followed_artists() {
-    oauth_client()
-        .await
-        .current_user_followed_artists(None, Some(10))
+    let client = oauth_client().await;
+    client
+        .current_user_followed_artists_manual(None, Some(10))
         .await
         .unwrap();
+    fetch_all(client.current_user_followed_artists()).await;
 }
 
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
@@ -184,11 +186,12 @@ async fn test_current_user_playing_track() {
 #[ignore]
 async fn test_current_user_recently_played() {
     let limit = TimeLimits::After(Utc::now() - Duration::days(2));
-    oauth_client()
-        .await
-        .current_user_recently_played(Some(10), Some(limit))
+    let client = oauth_client().await;
+    client
+        .current_user_recently_played_manual(Some(10), Some(limit))
         .await
         .unwrap();
+    fetch_all(client.current_user_recently_played(Some(limit))).await;
 }
 
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
@@ -262,6 +265,70 @@ async fn test_current_user_saved_tracks_add() {
         .unwrap();
 }
 
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_current_user_saved_episodes_add() {
+    let client = oauth_client().await;
+    let episode_ids = [
+        EpisodeId::from_id("0lbiy3LKzIY2fnyjioC11p").unwrap(),
+        EpisodeId::from_uri("spotify:episode:512ojhOuo1ktJprKbVcKyQ").unwrap(),
+    ];
+    client
+        .current_user_saved_episodes_add(episode_ids.iter().map(EpisodeId::as_ref))
+        .await
+        .unwrap();
+
+    let contains = client
+        .current_user_saved_episodes_contains(episode_ids.iter().map(EpisodeId::as_ref))
+        .await
+        .unwrap();
+    // Every episode should be saved
+    assert!(contains.into_iter().all(|x| x));
+
+    let all = fetch_all(client.current_user_saved_episodes(None)).await;
+    let all = all
+        .into_iter()
+        .map(|saved| saved.episode.id)
+        .collect::<Vec<_>>();
+    // All the initial episodes should appear
+    assert!(episode_ids.iter().all(|episode| all.contains(episode)));
+
+    client
+        .current_user_saved_episodes_delete(episode_ids)
+        .await
+        .unwrap();
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_current_user_saved_audiobooks_add() {
+    let client = oauth_client().await;
+    let audiobook_ids = [AudiobookId::from_id("7iHfbu1YPACw6oZPAFJtqe").unwrap()];
+    client
+        .current_user_saved_audiobooks_add(audiobook_ids.iter().map(AudiobookId::as_ref))
+        .await
+        .unwrap();
+
+    let contains = client
+        .current_user_saved_audiobooks_contains(audiobook_ids.iter().map(AudiobookId::as_ref))
+        .await
+        .unwrap();
+    // Every audiobook should be saved
+    assert!(contains.into_iter().all(|x| x));
+
+    let all = fetch_all(client.current_user_saved_audiobooks()).await;
+    let all = all.into_iter().map(|saved| saved.id).collect::<Vec<_>>();
+    // All the initial audiobooks should appear
+    assert!(audiobook_ids
+        .iter()
+        .all(|audiobook| all.contains(audiobook)));
+
+    client
+        .current_user_saved_audiobooks_delete(audiobook_ids)
+        .await
+        .unwrap();
+}
+
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
 #[ignore]
 async fn test_current_user_top_artists() {
@@ -519,6 +586,44 @@ async fn test_search_show() {
         .unwrap();
 }
 
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_search_query() {
+    let query = SearchQuery::new()
+        .album("arrival")
+        .artist("abba")
+        .not(SearchFilter::YearRange(1990, 2020));
+    oauth_client()
+        .await
+        .search(query, SearchType::Album, None, None, Some(10), Some(0))
+        .await
+        .unwrap();
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_search_multiple() {
+    let client = oauth_client().await;
+    let result = client
+        .search_multiple(
+            "abba",
+            [SearchType::Track, SearchType::Artist],
+            None,
+            None,
+            Some(2),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(result.albums.is_none());
+
+    // Each section is paginated on its own
+    let tracks = result.tracks.unwrap();
+    let next_tracks = client.next_page(&tracks).await.unwrap().unwrap();
+    assert_eq!(next_tracks.offset, 2);
+    assert!(result.artists.is_some());
+}
+
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
 #[ignore]
 async fn test_seek_track() {
@@ -800,11 +905,19 @@ async fn test_add_queue() {
 
     let birdy_uri =
         PlayableId::Track(TrackId::from_uri("spotify:track:6rqhFgbbKwnb9MLmUQDhG6").unwrap());
-    oauth_client()
+    let client = oauth_client().await;
+    client
+        .add_item_to_queue(birdy_uri.as_ref(), None)
         .await
-        .add_item_to_queue(birdy_uri, None)
+        .unwrap();
+
+    let queue = client.current_user_queue().await.unwrap();
+    assert!(queue.contains(&birdy_uri));
+    let added = client
+        .add_item_to_queue_if_missing(birdy_uri, None)
         .await
         .unwrap();
+    assert!(!added);
 }
 
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]